@@ -0,0 +1,264 @@
+pub mod allocations;
+#[cfg(feature = "serve")]
+pub mod serve;
+pub mod solution;
+pub mod trace;
+pub mod util;
+
+pub mod year2022;
+
+use std::time::Duration;
+
+use solution::{Part, Report, Solution};
+
+/// A day's solution compiled into the runner.
+pub struct Day {
+    pub year: u16,
+    pub number: u8,
+    /// Interchangeable implementations of the day, the first being the one
+    /// run by default.
+    pub variants: &'static [Variant],
+}
+
+impl Day {
+    pub fn default_variant(&self) -> &Variant {
+        &self.variants[0]
+    }
+
+    pub fn variant(&self, name: &str) -> anyhow::Result<&Variant> {
+        match self.variants.iter().find(|variant| variant.name == name) {
+            Some(variant) => Ok(variant),
+            None => {
+                let names: Vec<_> = self.variants.iter().map(|variant| variant.name).collect();
+                anyhow::bail!(
+                    "{self} has no variant {name:?}, try one of: {}",
+                    names.join(", ")
+                )
+            }
+        }
+    }
+
+    /// Solve `file` with every variant, returning what they agreed on for
+    /// each part and an error describing the differences if they did not.
+    pub fn crosscheck(&self, file: &str) -> anyhow::Result<Crosscheck> {
+        let mut answers: Vec<(&str, Vec<Result<String, String>>)> = Vec::new();
+        let mut timings = Vec::new();
+        for variant in self.variants {
+            let report = (variant.run)(file, &Part::ALL)?;
+            let elapsed = report.parse.elapsed
+                + report
+                    .parts
+                    .iter()
+                    .map(|part| part.measurement.elapsed)
+                    .sum::<Duration>();
+            timings.push((variant.name, elapsed));
+            let variant_answers = report
+                .parts
+                .into_iter()
+                .map(|part| part.answer.map_err(|error| error.to_string()))
+                .collect();
+            answers.push((variant.name, variant_answers));
+        }
+
+        let (_, expected) = &answers[0];
+        let mut agreed = Vec::new();
+        for (index, part) in Part::ALL.into_iter().enumerate() {
+            let agreement = match &expected[index] {
+                // Errors need not match, only that no variant got an answer.
+                Err(error) if answers.iter().all(|(_, found)| found[index].is_err()) => {
+                    Agreement::Unsolved(error.clone())
+                }
+                Ok(answer)
+                    if answers
+                        .iter()
+                        .all(|(_, found)| found[index] == expected[index]) =>
+                {
+                    Agreement::Agreed(answer.clone())
+                }
+                _ => {
+                    let found: Vec<_> = answers
+                        .iter()
+                        .map(|(name, found)| match &found[index] {
+                            Ok(answer) => format!("{name} = {answer}"),
+                            Err(error) => format!("{name} = error: {error}"),
+                        })
+                        .collect();
+                    anyhow::bail!(
+                        "{self} part {part}: variants disagree: {}",
+                        found.join(", ")
+                    );
+                }
+            };
+            agreed.push((part, agreement));
+        }
+
+        Ok(Crosscheck {
+            parts: agreed,
+            timings,
+        })
+    }
+}
+
+/// What every variant of a day made of the same input.
+#[derive(Debug)]
+pub struct Crosscheck {
+    pub parts: Vec<(Part, Agreement)>,
+    /// How long each variant took to parse the input and solve both parts.
+    pub timings: Vec<(&'static str, Duration)>,
+}
+
+/// How a crosscheck turned out for one part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Agreement {
+    /// Every variant gave this answer.
+    Agreed(String),
+    /// No variant gave an answer; this is the default variant's error.
+    Unsolved(String),
+}
+
+impl std::fmt::Display for Day {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} day{}", self.year, self.number)
+    }
+}
+
+/// One named implementation of a day.
+pub struct Variant {
+    pub name: &'static str,
+    pub run: fn(&str, &[Part]) -> anyhow::Result<Report>,
+}
+
+impl Variant {
+    pub const fn of<S: Solution>(name: &'static str) -> Self {
+        Self {
+            name,
+            run: solution::run::<S>,
+        }
+    }
+}
+
+/// Every day compiled in, across all years.
+pub fn days() -> impl Iterator<Item = &'static Day> {
+    year2022::DAYS.iter()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Length;
+
+    impl Solution for Length {
+        type Input = String;
+
+        fn parse(file: &str) -> anyhow::Result<Self::Input> {
+            Ok(file.to_string())
+        }
+
+        fn part1(input: &Self::Input) -> anyhow::Result<String> {
+            Ok(input.len().to_string())
+        }
+    }
+
+    struct OffByOne;
+
+    impl Solution for OffByOne {
+        type Input = String;
+
+        fn parse(file: &str) -> anyhow::Result<Self::Input> {
+            Ok(file.to_string())
+        }
+
+        fn part1(input: &Self::Input) -> anyhow::Result<String> {
+            Ok((input.len() + 1).to_string())
+        }
+    }
+
+    /// Like [`Length`], but with a second part too.
+    struct Solved;
+
+    impl Solution for Solved {
+        type Input = String;
+
+        fn parse(file: &str) -> anyhow::Result<Self::Input> {
+            Ok(file.to_string())
+        }
+
+        fn part1(input: &Self::Input) -> anyhow::Result<String> {
+            Ok(input.len().to_string())
+        }
+
+        fn part2(input: &Self::Input) -> anyhow::Result<String> {
+            Ok((input.len() * 2).to_string())
+        }
+    }
+
+    const AGREEING: Day = Day {
+        year: 2022,
+        number: 0,
+        variants: &[Variant::of::<Length>("a"), Variant::of::<Length>("b")],
+    };
+
+    const DISAGREEING: Day = Day {
+        year: 2022,
+        number: 0,
+        variants: &[
+            Variant::of::<Length>("length"),
+            Variant::of::<OffByOne>("off-by-one"),
+        ],
+    };
+
+    #[test]
+    fn crosscheck_agreeing_variants() {
+        let actual = AGREEING.crosscheck("abc").unwrap();
+
+        assert_eq!(
+            actual.parts[0],
+            (Part::One, Agreement::Agreed("3".to_string()))
+        );
+        let names: Vec<_> = actual.timings.iter().map(|&(name, _)| name).collect();
+        assert_eq!(names, ["a", "b"]);
+    }
+
+    #[test]
+    fn crosscheck_unsolved_part() {
+        let actual = AGREEING.crosscheck("abc").unwrap();
+
+        assert_eq!(
+            actual.parts[1],
+            (Part::Two, Agreement::Unsolved("not solved yet".to_string()))
+        );
+    }
+
+    #[test]
+    fn crosscheck_answer_against_error() {
+        const HALF_SOLVED: Day = Day {
+            year: 2022,
+            number: 0,
+            variants: &[
+                Variant::of::<Length>("length"),
+                Variant::of::<Solved>("solved"),
+            ],
+        };
+
+        let actual = HALF_SOLVED.crosscheck("abc").unwrap_err().to_string();
+        let expected =
+            "2022 day0 part 2: variants disagree: length = error: not solved yet, solved = 6";
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn crosscheck_disagreeing_variants() {
+        let actual = DISAGREEING.crosscheck("abc").unwrap_err().to_string();
+        let expected = "2022 day0 part 1: variants disagree: length = 3, off-by-one = 4";
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn unknown_variant() {
+        assert!(DISAGREEING.variant("length").is_ok());
+        assert!(DISAGREEING.variant("fast").is_err());
+    }
+}
@@ -2,14 +2,266 @@
 
 // extern crate test;
 
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-
-fn main() {
-    // day1::run();
-    // day2::run().unwrap();
-    // day3::run();
-    day4::run();
+use clap::{Parser, Subcommand, ValueEnum};
+
+use aoc::{solution::Part, trace, Agreement, Day};
+
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static ALLOCATOR: aoc::allocations::Counting = aoc::allocations::Counting;
+
+#[derive(Parser)]
+struct Args {
+    /// Only use the days of this year
+    #[arg(long, global = true)]
+    year: Option<u16>,
+
+    /// Print how each answer was reached to stderr, as text or, with
+    /// `--trace=json`, JSON lines
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "human"
+    )]
+    trace: Option<TraceFormat>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum TraceFormat {
+    Human,
+    Json,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve days with their default implementation, or a chosen variant
+    Run {
+        #[arg(long)]
+        day: Option<u8>,
+
+        /// Which implementation to use, e.g. `fast`, for the day given by `--day`
+        #[arg(long, requires = "day")]
+        variant: Option<String>,
+    },
+    /// Solve days with every implementation, fail if any answers differ and
+    /// show how long each took
+    Crosscheck {
+        #[arg(long)]
+        day: Option<u8>,
+    },
+    /// Solve days and check the answers against `input/{year}/day-{day}.answers`
+    Verify {
+        #[arg(long)]
+        day: Option<u8>,
+
+        /// Which implementation to use, e.g. `fast`, for the day given by `--day`
+        #[arg(long, requires = "day")]
+        variant: Option<String>,
+    },
+    /// Answer `POST /solve/{day}/{part}` requests on localhost
+    #[cfg(feature = "serve")]
+    Serve {
+        #[arg(long, default_value_t = 8022)]
+        port: u16,
+    },
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    match args.trace {
+        Some(TraceFormat::Human) => trace::print(trace::Format::Human),
+        Some(TraceFormat::Json) => trace::print(trace::Format::JsonLines),
+        None => {}
+    }
+    let command = args.command.unwrap_or(Command::Run {
+        day: None,
+        variant: None,
+    });
+
+    match command {
+        Command::Run { day, variant } => run(select_days(args.year, day)?, variant.as_deref()),
+        Command::Crosscheck { day } => crosscheck(select_days(args.year, day)?),
+        Command::Verify { day, variant } => {
+            verify(select_days(args.year, day)?, variant.as_deref())
+        }
+        #[cfg(feature = "serve")]
+        Command::Serve { port } => serve(port),
+    }
+}
+
+fn select_days(year: Option<u16>, number: Option<u8>) -> anyhow::Result<Vec<&'static Day>> {
+    let names = |days: &[&Day]| {
+        let names: Vec<_> = days.iter().map(|day| day.to_string()).collect();
+        names.join(", ")
+    };
+    let compiled: Vec<_> = aoc::days().collect();
+    println!("Days compiled in: {}", names(&compiled));
+
+    let days: Vec<_> = compiled
+        .into_iter()
+        .filter(|day| year.is_none_or(|year| day.year == year))
+        .filter(|day| number.is_none_or(|number| day.number == number))
+        .collect();
+    if days.is_empty() {
+        anyhow::bail!(
+            "No matching days compiled in, enable some with e.g. `--features year2022-day1`"
+        );
+    }
+    if year.is_some() || number.is_some() {
+        println!("Days selected: {}", names(&days));
+    }
+
+    Ok(days)
+}
+
+fn run(days: Vec<&Day>, variant: Option<&str>) -> anyhow::Result<()> {
+    for day in days {
+        let variant = match variant {
+            Some(name) => day.variant(name)?,
+            None => day.default_variant(),
+        };
+        let Some(file) = input(day)? else {
+            continue;
+        };
+
+        println!("{day} ({})", variant.name);
+        println!("{}", (variant.run)(&file, &Part::ALL)?);
+    }
+
+    Ok(())
+}
+
+/// The puzzle input for `day`, or `None` if it has not been downloaded.
+fn input(day: &Day) -> anyhow::Result<Option<String>> {
+    let path = aoc::util::input::path(day.year, day.number);
+    if !path.exists() {
+        println!("{day}: no input at {}, skipping", path.display());
+        return Ok(None);
+    }
+
+    aoc::util::input::read(day.year, day.number).map(Some)
+}
+
+fn crosscheck(days: Vec<&Day>) -> anyhow::Result<()> {
+    for day in days {
+        let Some(file) = input(day)? else {
+            continue;
+        };
+        let names: Vec<_> = day.variants.iter().map(|variant| variant.name).collect();
+
+        println!("{day} ({})", names.join(", "));
+        let crosscheck = day.crosscheck(&file)?;
+        for (part, agreement) in crosscheck.parts {
+            match agreement {
+                Agreement::Agreed(answer) => println!("  part {part}  agreed: {answer}"),
+                Agreement::Unsolved(error) => println!("  part {part}  unsolved: {error}"),
+            }
+        }
+        let width = names.iter().map(|name| name.len()).max().unwrap_or(0);
+        for (name, elapsed) in crosscheck.timings {
+            println!("  {name:<width$}  {elapsed:>10.1?}");
+        }
+    }
+
+    Ok(())
+}
+
+fn verify(days: Vec<&Day>, variant: Option<&str>) -> anyhow::Result<()> {
+    let (mut checked, mut wrong) = (0, 0);
+    for day in days {
+        let variant = match variant {
+            Some(name) => day.variant(name)?,
+            None => day.default_variant(),
+        };
+        let Some(file) = input(day)? else {
+            continue;
+        };
+        let Some(expected) = aoc::util::input::answers(day.year, day.number)? else {
+            let path = aoc::util::input::answers_path(day.year, day.number);
+            println!("{day}: no answers at {}, skipping", path.display());
+            continue;
+        };
+
+        let parts: Vec<_> = expected.iter().map(|&(part, _)| part).collect();
+        let report = (variant.run)(&file, &parts)?;
+        println!("{day} ({})", variant.name);
+        for (solved, (_, expected)) in report.parts.iter().zip(&expected) {
+            let part = solved.part;
+            checked += 1;
+            match &solved.answer {
+                Ok(answer) if answer == expected => println!("  part {part}  ok: {answer}"),
+                Ok(answer) => {
+                    wrong += 1;
+                    println!("  part {part}  wrong: {answer}, expected {expected}");
+                }
+                Err(error) => {
+                    wrong += 1;
+                    println!("  part {part}  error: {error}, expected {expected}");
+                }
+            }
+        }
+    }
+
+    if wrong > 0 {
+        anyhow::bail!("{wrong} of {checked} answers did not match");
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "serve")]
+fn serve(port: u16) -> anyhow::Result<()> {
+    let server = aoc::serve::Server::bind(port)?;
+    println!("Listening on http://{}", server.addr());
+
+    server.run()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Args {
+        Args::try_parse_from([&["aoc"], args].concat()).unwrap()
+    }
+
+    #[test]
+    fn trace_before_the_command() {
+        let args = parse(&["--trace", "run", "--day", "1"]);
+
+        assert!(matches!(args.trace, Some(TraceFormat::Human)));
+        assert!(matches!(
+            args.command,
+            Some(Command::Run { day: Some(1), .. })
+        ));
+    }
+
+    #[test]
+    fn trace_format() {
+        let before = parse(&["--trace=json", "run"]);
+        let after = parse(&["run", "--day", "2", "--trace=json"]);
+        let without = parse(&["run"]);
+
+        assert!(matches!(before.trace, Some(TraceFormat::Json)));
+        assert!(matches!(after.trace, Some(TraceFormat::Json)));
+        assert!(without.trace.is_none());
+    }
+
+    #[test]
+    fn variant_needs_a_day() {
+        let run = Args::try_parse_from(["aoc", "run", "--variant", "fast"]);
+        let verify = Args::try_parse_from(["aoc", "verify", "--variant", "fast"]);
+
+        assert!(run.is_err());
+        assert!(verify.is_err());
+        assert!(parse(&["run", "--day", "3", "--variant", "fast"])
+            .command
+            .is_some());
+    }
 }
@@ -0,0 +1,355 @@
+//! A local HTTP server answering `POST /solve/{day}/{part}`, with the puzzle
+//! input as the body, so other tools can use the solutions without shelling
+//! out to the runner.
+//!
+//! `?year=` and `?variant=` pick a year other than the latest with that day,
+//! or an implementation other than the default. `?trace=true` adds the
+//! solution's trace events to the diagnostics, at some cost to the timings.
+
+use std::net::SocketAddr;
+
+use serde_json::{json, Value};
+
+use crate::{
+    solution::{Measurement, Part},
+    trace, Day,
+};
+
+pub struct Server {
+    server: tiny_http::Server,
+    days: Vec<&'static Day>,
+}
+
+impl Server {
+    /// Listen on `port` on localhost only, answering for every day compiled
+    /// in; port 0 picks any free port.
+    pub fn bind(port: u16) -> anyhow::Result<Self> {
+        let server = tiny_http::Server::http(("127.0.0.1", port))
+            .map_err(|error| anyhow::anyhow!("Could not listen on port {port}. Error: {error}"))?;
+
+        Ok(Self {
+            server,
+            days: crate::days().collect(),
+        })
+    }
+
+    /// Answer for `days` instead of every day compiled in.
+    pub fn with_days(self, days: Vec<&'static Day>) -> Self {
+        Self { days, ..self }
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.server
+            .server_addr()
+            .to_ip()
+            .expect("listening on an IP address")
+    }
+
+    /// Answer requests until the process is stopped. A request that cannot
+    /// be answered, such as one whose client hung up, is logged and skipped.
+    pub fn run(&self) -> anyhow::Result<()> {
+        loop {
+            if let Err(error) = self.handle_next() {
+                eprintln!("Could not answer a request. Error: {error:#}");
+            }
+        }
+    }
+
+    /// Wait for a single request and answer it.
+    pub fn handle_next(&self) -> anyhow::Result<()> {
+        let mut request = self.server.recv()?;
+
+        let mut body = String::new();
+        let (status, response) = match request.as_reader().read_to_string(&mut body) {
+            Ok(_) => respond(&self.days, request.method().as_str(), request.url(), &body),
+            Err(error) => (
+                400,
+                json!({ "error": format!("Could not read body. Error: {error}") }),
+            ),
+        };
+
+        let header = tiny_http::Header::from_bytes("Content-Type", "application/json")
+            .expect("valid header");
+        let response = tiny_http::Response::from_string(response.to_string())
+            .with_status_code(status)
+            .with_header(header);
+        request.respond(response)?;
+
+        Ok(())
+    }
+}
+
+/// The status code and JSON body answering `method` on `url` with one of
+/// `days`.
+pub fn respond(days: &[&Day], method: &str, url: &str, body: &str) -> (u16, Value) {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let Some(route) = path.strip_prefix("/solve/") else {
+        return error(
+            404,
+            format!("No such route {path}, try POST /solve/{{day}}/{{part}}"),
+        );
+    };
+    if method != "POST" {
+        return error(405, format!("Use POST, not {method}"));
+    }
+
+    let Some((day, part)) = route.split_once('/') else {
+        return error(
+            404,
+            format!("No such route {path}, try POST /solve/{{day}}/{{part}}"),
+        );
+    };
+    let Ok(number) = day.parse::<u8>() else {
+        return error(400, format!("Bad day {day:?}"));
+    };
+    let part = match part {
+        "1" => Part::One,
+        "2" => Part::Two,
+        _ => return error(400, format!("Bad part {part:?}, expected 1 or 2")),
+    };
+
+    let mut year = None;
+    let mut variant = None;
+    let mut tracing = false;
+    for (key, value) in query.split('&').filter_map(|pair| pair.split_once('=')) {
+        match key {
+            "year" => match value.parse::<u16>() {
+                Ok(value) => year = Some(value),
+                Err(_) => return error(400, format!("Bad year {value:?}")),
+            },
+            "variant" => variant = Some(value),
+            "trace" => tracing = value == "true",
+            _ => return error(400, format!("Unknown parameter {key:?}")),
+        }
+    }
+
+    let Some(day) = find_day(days, year, number) else {
+        return error(404, format!("Day {number} is not compiled in"));
+    };
+    let variant = match variant {
+        Some(name) => match day.variant(name) {
+            Ok(variant) => variant,
+            Err(err) => return error(404, err.to_string()),
+        },
+        None => day.default_variant(),
+    };
+
+    // A solution may panic on input it does not expect, which should fail
+    // this request rather than stop the server.
+    let solve = || std::panic::catch_unwind(|| (variant.run)(body, &[part]));
+    let (report, events) = if tracing {
+        trace::collect(solve)
+    } else {
+        (solve(), Vec::new())
+    };
+    let report = match report {
+        Ok(Ok(report)) => report,
+        Ok(Err(err)) => return error(400, format!("Could not parse input. Error: {err:#}")),
+        Err(panic) => {
+            let message = panic
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "no message".to_string());
+            return error(500, format!("The solution panicked. Error: {message}"));
+        }
+    };
+    let [solved] = &report.parts[..] else {
+        unreachable!("asked for one part");
+    };
+
+    let mut response = json!({
+        "year": day.year,
+        "day": day.number,
+        "part": part.number(),
+        "variant": variant.name,
+        "answer": null,
+        "timing": {
+            "parse_ns": report.parse.elapsed.as_nanos() as u64,
+            "solve_ns": solved.measurement.elapsed.as_nanos() as u64,
+        },
+        "diagnostics": {},
+    });
+    if tracing {
+        let events: Vec<_> = events.iter().map(trace::Event::to_value).collect();
+        response["diagnostics"]["trace"] = events.into();
+    }
+    if cfg!(feature = "count-allocations") {
+        response["diagnostics"]["allocations"] = json!({
+            "parse": allocations(&report.parse),
+            "solve": allocations(&solved.measurement),
+        });
+    }
+
+    match &solved.answer {
+        Ok(answer) => {
+            response["answer"] = answer.as_str().into();
+            (200, response)
+        }
+        Err(err) => {
+            response["error"] = err.to_string().into();
+            (422, response)
+        }
+    }
+}
+
+/// The day in `year`, or in the latest year that has it.
+fn find_day<'a>(days: &[&'a Day], year: Option<u16>, number: u8) -> Option<&'a Day> {
+    days.iter()
+        .copied()
+        .filter(|day| day.number == number && year.is_none_or(|year| day.year == year))
+        .max_by_key(|day| day.year)
+}
+
+fn allocations(measurement: &Measurement) -> Value {
+    let stats = measurement.allocations;
+    json!({
+        "allocations": stats.allocations,
+        "bytes": stats.bytes,
+        "peak": stats.peak,
+    })
+}
+
+fn error(status: u16, message: String) -> (u16, Value) {
+    (status, json!({ "error": message }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{solution::Solution, Variant};
+    use std::{
+        io::{Read, Write},
+        net::TcpStream,
+    };
+
+    const EXAMPLE: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000";
+
+    /// Counts the bytes of its input, unless asked to panic.
+    struct Fragile;
+
+    impl Solution for Fragile {
+        type Input = String;
+
+        fn parse(file: &str) -> anyhow::Result<Self::Input> {
+            if file == "panic" {
+                panic!("asked to panic");
+            }
+            Ok(file.to_string())
+        }
+
+        fn part1(input: &Self::Input) -> anyhow::Result<String> {
+            Ok(input.len().to_string())
+        }
+    }
+
+    const FRAGILE: Day = Day {
+        year: 2022,
+        number: 0,
+        variants: &[Variant::of::<Fragile>("fragile")],
+    };
+
+    /// Answer with every day compiled in, as the server does.
+    #[cfg(feature = "year2022-day1")]
+    fn respond(method: &str, url: &str, body: &str) -> (u16, Value) {
+        super::respond(&crate::days().collect::<Vec<_>>(), method, url, body)
+    }
+
+    #[test]
+    #[cfg(feature = "year2022-day1")]
+    fn solves_part() {
+        let (status, response) = respond("POST", "/solve/1/2", EXAMPLE);
+
+        assert_eq!(status, 200);
+        assert_eq!(response["year"], 2022);
+        assert_eq!(response["answer"], "45000");
+        assert!(response["timing"]["solve_ns"].is_u64());
+        assert_eq!(response["diagnostics"]["trace"], Value::Null);
+    }
+
+    #[test]
+    #[cfg(feature = "year2022-day1")]
+    fn includes_trace() {
+        let (_, response) = respond("POST", "/solve/1/1?year=2022&trace=true", EXAMPLE);
+
+        let trace = response["diagnostics"]["trace"].as_array().unwrap();
+        assert_eq!(trace.len(), 5);
+        assert_eq!(trace[0], json!({ "event": "elf", "elf": 1, "total": 6000 }));
+    }
+
+    #[test]
+    #[cfg(feature = "year2022-day1")]
+    fn rejects_bad_requests() {
+        assert_eq!(respond("GET", "/solve/1/1", EXAMPLE).0, 405);
+        assert_eq!(respond("POST", "/solve/1/3", EXAMPLE).0, 400);
+        assert_eq!(respond("POST", "/solve/1/1?variant=nope", EXAMPLE).0, 404);
+        assert_eq!(respond("POST", "/solve/99/1", EXAMPLE).0, 404);
+        assert_eq!(respond("POST", "/other", EXAMPLE).0, 404);
+    }
+
+    #[test]
+    fn only_given_days() {
+        let days = [&FRAGILE];
+
+        assert_eq!(
+            super::respond(&days, "POST", "/solve/0/1", "abc").1["answer"],
+            "3"
+        );
+        assert_eq!(super::respond(&days, "POST", "/solve/1/1", EXAMPLE).0, 404);
+    }
+
+    /// Send `body` to `path` on `addr`, returning the status line and the
+    /// JSON body of the response.
+    fn post(addr: SocketAddr, path: &str, body: &str) -> (String, Value) {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "POST {path} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.lines().next().unwrap().to_string();
+        (status, serde_json::from_str(body).unwrap())
+    }
+
+    #[test]
+    #[cfg(feature = "year2022-day1")]
+    fn answers_over_localhost() {
+        let server = Server::bind(0).unwrap();
+        let addr = server.addr();
+        assert!(addr.ip().is_loopback());
+        let handle = std::thread::spawn(move || server.handle_next());
+
+        let (status, body) = post(addr, "/solve/1/1", EXAMPLE);
+        handle.join().unwrap().unwrap();
+
+        assert!(status.starts_with("HTTP/1.1 200"), "{status}");
+        assert_eq!(body["answer"], "24000");
+    }
+
+    #[test]
+    fn survives_a_panicking_solution() {
+        let server = Server::bind(0).unwrap().with_days(vec![&FRAGILE]);
+        let addr = server.addr();
+        let handle = std::thread::spawn(move || {
+            server.handle_next()?;
+            server.handle_next()
+        });
+
+        let (status, body) = post(addr, "/solve/0/1", "panic");
+        assert!(status.starts_with("HTTP/1.1 500"), "{status}");
+        assert_eq!(
+            body["error"],
+            "The solution panicked. Error: asked to panic"
+        );
+
+        let (status, body) = post(addr, "/solve/0/1", "abc");
+        handle.join().unwrap().unwrap();
+        assert!(status.starts_with("HTTP/1.1 200"), "{status}");
+        assert_eq!(body["answer"], "3");
+    }
+}
@@ -0,0 +1,129 @@
+use std::{fmt, time::Duration, time::Instant};
+
+use crate::allocations;
+
+/// A day's puzzle, split into a parse phase and a solve phase per part so the
+/// runner can measure each of them separately.
+pub trait Solution {
+    type Input;
+
+    fn parse(file: &str) -> anyhow::Result<Self::Input>;
+
+    fn part1(input: &Self::Input) -> anyhow::Result<String>;
+
+    fn part2(_input: &Self::Input) -> anyhow::Result<String> {
+        anyhow::bail!("not solved yet")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// Wall time and allocation counts for one phase of a solution.
+#[derive(Debug, Clone, Copy)]
+pub struct Measurement {
+    pub elapsed: Duration,
+    pub allocations: allocations::Stats,
+}
+
+impl fmt::Display for Measurement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:>10.1?}", self.elapsed)?;
+        if cfg!(feature = "count-allocations") {
+            write!(f, "  {}", self.allocations)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Measurement) {
+    let start = Instant::now();
+    let (out, allocations) = allocations::measure(f);
+    let elapsed = start.elapsed();
+
+    (
+        out,
+        Measurement {
+            elapsed,
+            allocations,
+        },
+    )
+}
+
+/// The answer to one part, and what it took to get there.
+#[derive(Debug)]
+pub struct PartReport {
+    pub part: Part,
+    pub answer: anyhow::Result<String>,
+    pub measurement: Measurement,
+}
+
+/// Everything measured while parsing a file and solving some of its parts.
+#[derive(Debug)]
+pub struct Report {
+    pub parse: Measurement,
+    pub parts: Vec<PartReport>,
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "  parse  {}", self.parse)?;
+        for PartReport {
+            part,
+            answer,
+            measurement,
+        } in &self.parts
+        {
+            match answer {
+                Ok(answer) => write!(f, "\n  part {part} {measurement}  answer: {answer}")?,
+                Err(error) => write!(f, "\n  part {part} {measurement}  error: {error}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parse `file` and solve `parts` of it with `S`, measuring each phase. Fails
+/// only if the file cannot be parsed; each part's answer may still be an
+/// error.
+pub fn run<S: Solution>(file: &str, parts: &[Part]) -> anyhow::Result<Report> {
+    let (input, parse) = measure(|| S::parse(file));
+    let input = input?;
+
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let (answer, measurement) = measure(|| match part {
+                Part::One => S::part1(&input),
+                Part::Two => S::part2(&input),
+            });
+            PartReport {
+                part,
+                answer,
+                measurement,
+            }
+        })
+        .collect();
+
+    Ok(Report { parse, parts })
+}
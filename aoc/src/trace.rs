@@ -0,0 +1,148 @@
+//! Structured events emitted by solutions while they work, to explain how an
+//! answer was reached.
+//!
+//! Events go nowhere unless a sink is set up for the current thread with
+//! [`print`] or [`collect`], and the [`trace!`](crate::trace!) macro does not
+//! even build them in that case.
+
+use std::{cell::RefCell, fmt};
+
+pub use serde_json::Value;
+
+/// One step of a solution, e.g. an elf and their total calories.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    pub name: &'static str,
+    pub fields: Vec<(&'static str, Value)>,
+}
+
+impl Event {
+    /// The event as a JSON object, with its name under `"event"`.
+    pub fn to_value(&self) -> Value {
+        let mut object = serde_json::Map::new();
+        object.insert("event".to_string(), self.name.into());
+        for (key, value) in &self.fields {
+            object.insert(key.to_string(), value.clone());
+        }
+        Value::Object(object)
+    }
+
+    /// The event as a single line of JSON.
+    pub fn to_json(&self) -> String {
+        self.to_value().to_string()
+    }
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        for (key, value) in &self.fields {
+            write!(f, " {key}={value}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Human,
+    JsonLines,
+}
+
+enum Sink {
+    Print(Format),
+    Collect(Vec<Event>),
+}
+
+thread_local! {
+    static SINK: RefCell<Option<Sink>> = const { RefCell::new(None) };
+}
+
+pub fn enabled() -> bool {
+    SINK.with(|sink| sink.borrow().is_some())
+}
+
+/// Print every event emitted on this thread to stderr from now on, keeping
+/// stdout for the answers.
+pub fn print(format: Format) {
+    SINK.set(Some(Sink::Print(format)));
+}
+
+/// Run `f`, gathering the events it emits on this thread instead of sending
+/// them wherever they were going before.
+pub fn collect<T>(f: impl FnOnce() -> T) -> (T, Vec<Event>) {
+    let previous = SINK.replace(Some(Sink::Collect(Vec::new())));
+    let out = f();
+    let events = match SINK.replace(previous) {
+        Some(Sink::Collect(events)) => events,
+        _ => Vec::new(),
+    };
+
+    (out, events)
+}
+
+pub fn emit(name: &'static str, fields: Vec<(&'static str, Value)>) {
+    SINK.with(|sink| match &mut *sink.borrow_mut() {
+        Some(Sink::Print(Format::Human)) => eprintln!("{}", Event { name, fields }),
+        Some(Sink::Print(Format::JsonLines)) => eprintln!("{}", Event { name, fields }.to_json()),
+        Some(Sink::Collect(events)) => events.push(Event { name, fields }),
+        None => {}
+    })
+}
+
+/// Emit an event with named fields, e.g.
+/// `trace!("elf", index = 2, total = 24000)`. Field values can be anything
+/// that converts into a JSON [`Value`].
+#[macro_export]
+macro_rules! trace {
+    ($name:expr $(, $key:ident = $value:expr)* $(,)?) => {
+        if $crate::trace::enabled() {
+            $crate::trace::emit(
+                $name,
+                vec![$((stringify!($key), $crate::trace::Value::from($value))),*],
+            );
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collects_events() {
+        let (answer, events) = collect(|| {
+            crate::trace!("step", index = 1, letter = "p");
+            crate::trace!("done");
+            42
+        });
+
+        assert_eq!(answer, 42);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].to_string(), r#"step index=1 letter="p""#);
+        assert_eq!(events[1].to_string(), "done");
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        assert!(!enabled());
+
+        let (_, events) = collect(|| assert!(enabled()));
+
+        assert!(events.is_empty());
+        assert!(!enabled());
+    }
+
+    #[test]
+    fn json_line() {
+        let event = Event {
+            name: "pair",
+            fields: vec![("index", 3.into()), ("contained", true.into())],
+        };
+
+        let actual = event.to_json();
+        let expected = r#"{"event":"pair","index":3,"contained":true}"#;
+
+        assert_eq!(actual, expected);
+    }
+}
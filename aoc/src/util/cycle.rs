@@ -0,0 +1,121 @@
+//! Finding where a sequence of states starts repeating, and using that to
+//! skip ahead to a step far beyond what could be simulated.
+
+use std::{collections::HashMap, hash::Hash};
+
+/// A sequence that, from step `start` on, repeats every `length` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The step no later than the end of the first time around that is in
+    /// the same state as step `n`.
+    pub fn equivalent(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+
+    /// The value at step `n` of something that grows by the same amount
+    /// each time around the cycle, such as a height, given its value at
+    /// every step up to the end of the first time around.
+    ///
+    /// # Panics
+    ///
+    /// If `values` stops before step `start + length`.
+    pub fn extrapolate(&self, values: &[u64], n: usize) -> u64 {
+        if n < values.len() {
+            return values[n];
+        }
+
+        let per_cycle = values[self.start + self.length] - values[self.start];
+        let cycles = ((n - self.start) / self.length) as u64;
+
+        values[self.equivalent(n)] + cycles * per_cycle
+    }
+}
+
+/// The first repeat in `keys`, where the key at index `i` stands for the
+/// state after `i` steps and states with equal keys go on the same way.
+///
+/// Stops reading `keys` as soon as one repeats, so they can come from a
+/// simulation that runs for as long as it is asked.
+pub fn find<K: Eq + Hash>(keys: impl IntoIterator<Item = K>) -> Option<Cycle> {
+    let mut seen = HashMap::new();
+    for (step, key) in keys.into_iter().enumerate() {
+        if let Some(start) = seen.insert(key, step) {
+            return Some(Cycle {
+                start,
+                length: step - start,
+            });
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_repeat() {
+        // 1, 2, then 4, 8, 16, 12 over and over, doubling modulo 20.
+        let keys = std::iter::successors(Some(1u32), |x| Some(x * 2 % 20));
+
+        assert_eq!(
+            find(keys),
+            Some(Cycle {
+                start: 2,
+                length: 4
+            })
+        );
+    }
+
+    #[test]
+    fn no_repeat() {
+        assert_eq!(find(0..100), None);
+    }
+
+    #[test]
+    fn equivalent_step() {
+        let cycle = Cycle {
+            start: 3,
+            length: 4,
+        };
+
+        assert_eq!(cycle.equivalent(2), 2);
+        assert_eq!(cycle.equivalent(3), 3);
+        assert_eq!(cycle.equivalent(7), 3);
+        assert_eq!(cycle.equivalent(1_000_000_000_002), 6);
+    }
+
+    #[test]
+    fn extrapolate_growth() {
+        // Two steps of 1 to begin with, then 1, 2, 3 over and over.
+        let values: Vec<u64> = vec![0, 1, 2, 3, 5, 8, 9];
+        let cycle = Cycle {
+            start: 2,
+            length: 3,
+        };
+        let simulated: Vec<u64> = (0..20)
+            .scan(0, |total, step| {
+                let value = *total;
+                *total += match step {
+                    0 | 1 => 1,
+                    _ => (step - 2) % 3 + 1,
+                };
+                Some(value)
+            })
+            .collect();
+
+        for (n, &expected) in simulated.iter().enumerate() {
+            assert_eq!(cycle.extrapolate(&values, n), expected, "step {n}");
+        }
+    }
+}
@@ -0,0 +1,419 @@
+//! A dense, row-major 2D grid for puzzles drawn as a block of characters.
+
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+/// A position in a grid, `x` counts columns from the left and `y` counts
+/// rows from the top.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Point {
+    pub fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+
+    /// Move by `(dx, dy)`, or `None` if that would go below zero.
+    pub fn offset(self, (dx, dy): (isize, isize)) -> Option<Self> {
+        Some(Self {
+            x: self.x.checked_add_signed(dx)?,
+            y: self.y.checked_add_signed(dy)?,
+        })
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// Steps to the 4 orthogonal neighbours: up, right, down, left.
+pub const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Steps to all 8 neighbours, clockwise from up.
+pub const ALL_DIRECTIONS: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Parse a block of text, one row per line, converting each character
+    /// with `cell`. Every line must be the same width.
+    pub fn parse(
+        text: &str,
+        mut cell: impl FnMut(char) -> anyhow::Result<T>,
+    ) -> anyhow::Result<Self> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::with_capacity(text.len());
+
+        for (y, line) in text.lines().enumerate() {
+            let mut line_width = 0;
+            for (x, c) in line.chars().enumerate() {
+                match cell(c) {
+                    Ok(value) => cells.push(value),
+                    Err(error) => anyhow::bail!("line {}, column {}: {error}", y + 1, x + 1),
+                }
+                line_width += 1;
+            }
+
+            match width {
+                None => width = Some(line_width),
+                Some(width) if width != line_width => {
+                    anyhow::bail!("line {} is {line_width} wide, expected {width}", y + 1)
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point.x < self.width && point.y < self.height
+    }
+
+    fn index_of(&self, point: Point) -> Option<usize> {
+        self.contains(point)
+            .then_some(point.y * self.width + point.x)
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.index_of(point).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.index_of(point).map(|index| &mut self.cells[index])
+    }
+
+    /// Replace the value at `point`, returning the old one, or `None` if the
+    /// point is outside the grid.
+    pub fn set(&mut self, point: Point, value: T) -> Option<T> {
+        self.get_mut(point)
+            .map(|cell| std::mem::replace(cell, value))
+    }
+
+    /// Every point in the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    /// Every cell with its point, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    /// The first point whose cell matches `predicate`.
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(point, _)| point)
+    }
+
+    pub fn row(&self, y: usize) -> impl Iterator<Item = &T> {
+        let row = if y < self.height {
+            &self.cells[y * self.width..(y + 1) * self.width]
+        } else {
+            &[]
+        };
+        row.iter()
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let height = if x < self.width { self.height } else { 0 };
+        (0..height).map(move |y| &self.cells[y * self.width + x])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a zero chunk size, which an empty grid would give.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Points reached by repeatedly stepping by `step` from `from`, not
+    /// including `from` itself, until falling off the grid.
+    pub fn ray(&self, from: Point, step: (isize, isize)) -> impl Iterator<Item = Point> + '_ {
+        std::iter::successors(Some(from), move |&point| point.offset(step))
+            .skip(1)
+            .take_while(|&point| self.contains(point))
+    }
+
+    fn neighbours<'a>(
+        &'a self,
+        point: Point,
+        steps: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = Point> + 'a {
+        steps
+            .iter()
+            .filter_map(move |&step| point.offset(step))
+            .filter(|&neighbour| self.contains(neighbour))
+    }
+
+    /// The up to 4 orthogonal neighbours of `point` that are in the grid.
+    pub fn neighbours4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours(point, &ORTHOGONAL)
+    }
+
+    /// The up to 8 neighbours of `point`, including diagonals, that are in
+    /// the grid.
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours(point, &ALL_DIRECTIONS)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Swap rows and columns.
+    pub fn transpose(&self) -> Self {
+        self.remap(self.height, self.width, |Point { x, y }| Point::new(y, x))
+    }
+
+    /// Rotate a quarter turn clockwise, so the first column becomes the
+    /// first row read bottom to top.
+    pub fn rotate_clockwise(&self) -> Self {
+        let height = self.height;
+        self.remap(height, self.width, |Point { x, y }| {
+            Point::new(y, height - 1 - x)
+        })
+    }
+
+    /// Rotate a quarter turn anticlockwise, so the last column becomes the
+    /// first row.
+    pub fn rotate_anticlockwise(&self) -> Self {
+        let width = self.width;
+        self.remap(self.height, width, |Point { x, y }| {
+            Point::new(width - 1 - y, x)
+        })
+    }
+
+    /// Build a `width` x `height` grid where each point takes the value at
+    /// `source(point)` in this grid.
+    fn remap(&self, width: usize, height: usize, source: impl Fn(Point) -> Point) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Point::new(x, y)))
+            .map(|point| self[source(point)].clone())
+            .collect();
+
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{point} is outside the {}x{} grid", self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{point} is outside the {width}x{height} grid"))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(text: &str) -> Grid<char> {
+        Grid::parse(text, Ok).unwrap()
+    }
+
+    #[test]
+    fn parse_non_square() {
+        let grid = chars("abc\ndef");
+
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid[Point::new(2, 1)], 'f');
+    }
+
+    #[test]
+    fn parse_empty() {
+        let grid = chars("");
+
+        assert_eq!(grid.width(), 0);
+        assert_eq!(grid.height(), 0);
+        assert!(grid.is_empty());
+        assert_eq!(grid.get(Point::new(0, 0)), None);
+        assert_eq!(grid.points().count(), 0);
+        assert_eq!(grid.to_string(), "");
+    }
+
+    #[test]
+    fn parse_ragged() {
+        let actual = Grid::parse("abc\nde", Ok);
+
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn parse_bad_cell() {
+        let actual = Grid::parse("12\n3x", |c| {
+            c.to_digit(10)
+                .ok_or_else(|| anyhow::anyhow!("not a digit: {c}"))
+        });
+
+        let error = actual.unwrap_err().to_string();
+        assert_eq!(error, "line 2, column 2: not a digit: x");
+    }
+
+    #[test]
+    fn get_and_set_are_bounds_checked() {
+        let mut grid = chars("abc\ndef");
+
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(0, 2)), None);
+        assert_eq!(grid.set(Point::new(3, 0), 'z'), None);
+
+        assert_eq!(grid.set(Point::new(1, 1), 'z'), Some('e'));
+        assert_eq!(grid.to_string(), "abc\ndzf");
+    }
+
+    #[test]
+    fn neighbours_at_corner() {
+        let grid = chars("abc\ndef");
+
+        let actual: Vec<_> = grid.neighbours4(Point::new(0, 0)).collect();
+        let expected = vec![Point::new(1, 0), Point::new(0, 1)];
+        assert_eq!(actual, expected);
+
+        let actual: Vec<_> = grid.neighbours8(Point::new(2, 1)).collect();
+        let expected = vec![Point::new(2, 0), Point::new(1, 1), Point::new(1, 0)];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = chars("abc\ndef");
+
+        let actual: String = grid.row(1).collect();
+        assert_eq!(actual, "def");
+
+        let actual: String = grid.column(2).collect();
+        assert_eq!(actual, "cf");
+
+        assert_eq!(grid.row(2).count(), 0);
+        assert_eq!(grid.column(3).count(), 0);
+        assert_eq!(grid.rows().count(), 2);
+    }
+
+    #[test]
+    fn ray_stops_at_edge() {
+        let grid = chars("abc\ndef\nghi");
+
+        let actual: String = grid
+            .ray(Point::new(0, 0), (1, 1))
+            .map(|point| grid[point])
+            .collect();
+        assert_eq!(actual, "ei");
+
+        let actual: Vec<_> = grid.ray(Point::new(1, 0), (0, -1)).collect();
+        assert_eq!(actual, vec![]);
+    }
+
+    #[test]
+    fn transpose_non_square() {
+        let grid = chars("abc\ndef");
+
+        let actual = grid.transpose().to_string();
+        assert_eq!(actual, "ad\nbe\ncf");
+    }
+
+    #[test]
+    fn rotate_non_square() {
+        let grid = chars("abc\ndef");
+
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_anticlockwise().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_clockwise().rotate_anticlockwise(), grid);
+    }
+
+    #[test]
+    fn transform_empty() {
+        let grid = chars("");
+
+        assert!(grid.transpose().is_empty());
+        assert!(grid.rotate_clockwise().is_empty());
+    }
+
+    #[test]
+    fn display_round_trips() {
+        let text = "#..#\n.##.\n#..#";
+
+        assert_eq!(chars(text).to_string(), text);
+    }
+}
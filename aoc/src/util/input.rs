@@ -0,0 +1,89 @@
+use std::path::PathBuf;
+
+use crate::solution::Part;
+
+/// Where the puzzle input for a day lives, relative to the crate root.
+pub fn path(year: u16, day: u8) -> PathBuf {
+    PathBuf::from(format!("input/{year}/day-{day}.txt"))
+}
+
+pub fn read(year: u16, day: u8) -> anyhow::Result<String> {
+    let path = path(year, day);
+    match std::fs::read_to_string(&path) {
+        Ok(contents) => Ok(contents),
+        Err(error) => anyhow::bail!("Could not open {}. Error: {error}", path.display()),
+    }
+}
+
+/// Where the accepted answers for a day live, next to its input.
+pub fn answers_path(year: u16, day: u8) -> PathBuf {
+    PathBuf::from(format!("input/{year}/day-{day}.answers"))
+}
+
+/// The accepted answers for a day, or `None` if none have been recorded.
+pub fn answers(year: u16, day: u8) -> anyhow::Result<Option<Vec<(Part, String)>>> {
+    let path = answers_path(year, day);
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(error) => anyhow::bail!("Could not open {}. Error: {error}", path.display()),
+    };
+    match parse_answers(&contents) {
+        Ok(answers) => Ok(Some(answers)),
+        Err(error) => anyhow::bail!("{}: {error}", path.display()),
+    }
+}
+
+/// One answer per line, part 1 first. A blank line skips a part that has no
+/// accepted answer yet.
+fn parse_answers(contents: &str) -> anyhow::Result<Vec<(Part, String)>> {
+    let lines: Vec<_> = contents.lines().map(str::trim).collect();
+    if lines.len() > Part::ALL.len() {
+        anyhow::bail!(
+            "line {}: there are only {} parts",
+            Part::ALL.len() + 1,
+            Part::ALL.len()
+        );
+    }
+
+    Ok(Part::ALL
+        .into_iter()
+        .zip(lines)
+        .filter(|(_, answer)| !answer.is_empty())
+        .map(|(part, answer)| (part, answer.to_string()))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_by_part() {
+        let actual = parse_answers("71934\n211447\n").unwrap();
+        let expected = vec![
+            (Part::One, "71934".to_string()),
+            (Part::Two, "211447".to_string()),
+        ];
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn missing_answers() {
+        let actual = parse_answers("\nEHZFZHCZ\n").unwrap();
+
+        assert_eq!(actual, vec![(Part::Two, "EHZFZHCZ".to_string())]);
+        assert_eq!(parse_answers("").unwrap(), vec![]);
+    }
+
+    #[test]
+    fn too_many_answers() {
+        let actual = parse_answers("1\n2\n3\n").unwrap_err().to_string();
+
+        assert_eq!(actual, "line 3: there are only 2 parts");
+    }
+}
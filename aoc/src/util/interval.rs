@@ -0,0 +1,371 @@
+//! Sets of integers stored as sorted, disjoint inclusive ranges.
+
+use std::{fmt, ops::RangeInclusive};
+
+/// A set of integers kept as sorted ranges that neither overlap nor touch,
+/// so `1..=3` and `4..=6` are stored as `1..=6`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+    intervals: Vec<(i64, i64)>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sort and merge any collection of possibly overlapping ranges.
+    fn normalise(mut intervals: Vec<(i64, i64)>) -> Self {
+        intervals.retain(|(start, end)| start <= end);
+        intervals.sort_unstable();
+
+        let mut merged: Vec<(i64, i64)> = Vec::with_capacity(intervals.len());
+        for (start, end) in intervals {
+            match merged.last_mut() {
+                Some(last) if start <= last.1.saturating_add(1) => last.1 = last.1.max(end),
+                _ => merged.push((start, end)),
+            }
+        }
+
+        Self { intervals: merged }
+    }
+
+    /// Add a range, merging it with only the ranges it overlaps or touches,
+    /// which are found by binary search.
+    pub fn insert(&mut self, range: RangeInclusive<i64>) {
+        let (mut start, mut end) = range.into_inner();
+        if start > end {
+            return;
+        }
+
+        // The first range reaching `start`, and the first starting past `end`.
+        let first = self
+            .intervals
+            .partition_point(|&(_, existing)| existing.saturating_add(1) < start);
+        let after = self
+            .intervals
+            .partition_point(|&(existing, _)| existing <= end.saturating_add(1));
+        if first < after {
+            start = start.min(self.intervals[first].0);
+            end = end.max(self.intervals[after - 1].1);
+        }
+
+        self.intervals.splice(first..after, [(start, end)]);
+    }
+
+    /// The disjoint ranges making up the set, in order.
+    pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<i64>> + '_ {
+        self.intervals.iter().map(|&(start, end)| start..=end)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// How many integers are in the set, which can be one more than fits in
+    /// a `u64` when the set is every `i64`.
+    pub fn len(&self) -> u128 {
+        self.intervals
+            .iter()
+            .map(|&(start, end)| u128::from(end.abs_diff(start)) + 1)
+            .sum()
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        let after = self.intervals.partition_point(|&(start, _)| start <= value);
+        after > 0 && self.intervals[after - 1].1 >= value
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let intervals = self
+            .intervals
+            .iter()
+            .chain(&other.intervals)
+            .copied()
+            .collect();
+        Self::normalise(intervals)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (mut i, mut j) = (0, 0);
+        let mut intervals = Vec::new();
+
+        while let (Some(&(a_start, a_end)), Some(&(b_start, b_end))) =
+            (self.intervals.get(i), other.intervals.get(j))
+        {
+            let (start, end) = (a_start.max(b_start), a_end.min(b_end));
+            if start <= end {
+                intervals.push((start, end));
+            }
+            if a_end < b_end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Self { intervals }
+    }
+
+    /// Everything in this set that is not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        let mut j = 0;
+
+        for &(start, end) in &self.intervals {
+            // Skip past what ends before this interval; what is left may still
+            // reach into the next one, so is not consumed here.
+            while other
+                .intervals
+                .get(j)
+                .is_some_and(|&(_, cut_end)| cut_end < start)
+            {
+                j += 1;
+            }
+
+            let mut from = Some(start);
+            for &(cut_start, cut_end) in &other.intervals[j..] {
+                let Some(current) = from.filter(|&current| cut_start <= end && current <= end)
+                else {
+                    break;
+                };
+                if cut_start > current {
+                    intervals.push((current, cut_start - 1));
+                }
+                from = if cut_end < end {
+                    Some(cut_end + 1)
+                } else {
+                    None
+                };
+            }
+            if let Some(current) = from {
+                intervals.push((current, end));
+            }
+        }
+
+        Self { intervals }
+    }
+
+    /// Whether every integer in this set is also in `other`.
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.difference(other).is_empty()
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    /// The parts of `window` that are not in the set.
+    pub fn gaps(&self, window: RangeInclusive<i64>) -> Self {
+        Self::from(window).difference(self)
+    }
+}
+
+/// The ranges in order, like `-2..=4, 7..=7`, or `{}` when empty.
+impl fmt::Display for IntervalSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "{{}}");
+        }
+        for (index, (start, end)) in self.intervals.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{start}..={end}")?;
+        }
+        Ok(())
+    }
+}
+
+impl From<RangeInclusive<i64>> for IntervalSet {
+    fn from(range: RangeInclusive<i64>) -> Self {
+        Self::normalise(vec![range.into_inner()])
+    }
+}
+
+impl FromIterator<RangeInclusive<i64>> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<i64>>>(ranges: I) -> Self {
+        Self::normalise(ranges.into_iter().map(RangeInclusive::into_inner).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(set: &IntervalSet) -> Vec<RangeInclusive<i64>> {
+        set.iter().collect()
+    }
+
+    #[test]
+    fn merges_overlapping_and_touching() {
+        let set: IntervalSet = [5..=8, 1..=3, 4..=4, 12..=15, 14..=20]
+            .into_iter()
+            .collect();
+
+        assert_eq!(ranges(&set), vec![1..=8, 12..=20]);
+        assert_eq!(set.len(), 17);
+    }
+
+    #[test]
+    fn ignores_empty_ranges() {
+        #[allow(clippy::reversed_empty_ranges)]
+        let set = IntervalSet::from(5..=4);
+
+        assert!(set.is_empty());
+        assert_eq!(set.len(), 0);
+    }
+
+    #[test]
+    fn insert_merges() {
+        let mut set = IntervalSet::new();
+        set.insert(10..=12);
+        set.insert(1..=2);
+        set.insert(3..=9);
+
+        assert_eq!(ranges(&set), vec![1..=12]);
+    }
+
+    #[test]
+    fn insert_between_and_across() {
+        let mut set: IntervalSet = [1..=2, 6..=7, 10..=11, 20..=30].into_iter().collect();
+        set.insert(4..=4);
+        set.insert(8..=9);
+        #[allow(clippy::reversed_empty_ranges)]
+        set.insert(15..=14);
+
+        assert_eq!(ranges(&set), vec![1..=2, 4..=4, 6..=11, 20..=30]);
+
+        set.insert(3..=25);
+        set.insert(i64::MIN..=0);
+        set.insert(31..=i64::MAX);
+
+        assert_eq!(ranges(&set), vec![i64::MIN..=i64::MAX]);
+    }
+
+    #[test]
+    fn contains() {
+        let set: IntervalSet = [1..=3, 7..=9].into_iter().collect();
+
+        let actual: Vec<_> = (0..=10).filter(|&value| set.contains(value)).collect();
+        let expected = vec![1, 2, 3, 7, 8, 9];
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn union() {
+        let a: IntervalSet = [1..=3, 10..=12].into_iter().collect();
+        let b: IntervalSet = [2..=5, 20..=20].into_iter().collect();
+
+        assert_eq!(ranges(&a.union(&b)), vec![1..=5, 10..=12, 20..=20]);
+    }
+
+    #[test]
+    fn intersection() {
+        let a: IntervalSet = [1..=5, 8..=12].into_iter().collect();
+        let b: IntervalSet = [3..=9, 12..=14].into_iter().collect();
+
+        assert_eq!(ranges(&a.intersection(&b)), vec![3..=5, 8..=9, 12..=12]);
+        assert!(a.overlaps(&b));
+        assert!(!a.overlaps(&IntervalSet::from(6..=7)));
+    }
+
+    #[test]
+    fn difference_splits() {
+        let a = IntervalSet::from(1..=20);
+        let b: IntervalSet = [0..=2, 5..=6, 10..=12, 19..=25].into_iter().collect();
+
+        assert_eq!(ranges(&a.difference(&b)), vec![3..=4, 7..=9, 13..=18]);
+    }
+
+    #[test]
+    fn difference_spanning_several() {
+        let a: IntervalSet = [1..=3, 6..=8, 11..=13].into_iter().collect();
+        let b: IntervalSet = [2..=12].into_iter().collect();
+
+        assert_eq!(ranges(&a.difference(&b)), vec![1..=1, 13..=13]);
+    }
+
+    #[test]
+    fn subset() {
+        let a = IntervalSet::from(2..=4);
+        let b = IntervalSet::from(1..=5);
+
+        assert!(a.is_subset(&b));
+        assert!(!b.is_subset(&a));
+    }
+
+    #[test]
+    fn gaps_in_window() {
+        let set: IntervalSet = [-5..=2, 4..=6, 9..=30].into_iter().collect();
+
+        assert_eq!(ranges(&set.gaps(0..=20)), vec![3..=3, 7..=8]);
+        assert_eq!(ranges(&IntervalSet::new().gaps(0..=3)), vec![0..=3]);
+    }
+
+    #[test]
+    fn display() {
+        let set: IntervalSet = [7..=7, -2..=4].into_iter().collect();
+
+        assert_eq!(set.to_string(), "-2..=4, 7..=7");
+        assert_eq!(IntervalSet::new().to_string(), "{}");
+    }
+
+    #[test]
+    fn extremes_do_not_overflow() {
+        let set: IntervalSet = [i64::MIN..=0, 1..=i64::MAX].into_iter().collect();
+
+        assert_eq!(ranges(&set), vec![i64::MIN..=i64::MAX]);
+        assert_eq!(set.len(), 1 << 64);
+        assert!(set.gaps(0..=10).is_empty());
+        assert!(IntervalSet::from(0..=i64::MAX).difference(&set).is_empty());
+    }
+
+    #[test]
+    fn many_ranges_match_brute_force() {
+        // A cheap deterministic spread of a few thousand short ranges.
+        let mut seed: u64 = 17;
+        let mut next = || {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) as i64
+        };
+        let a: Vec<_> = (0..3000)
+            .map(|_| {
+                let start = next() % 50_000;
+                start..=start + next() % 20
+            })
+            .collect();
+        let b: Vec<_> = (0..3000)
+            .map(|_| {
+                let start = next() % 50_000;
+                start..=start + next() % 20
+            })
+            .collect();
+
+        let mut in_a = vec![false; 50_100];
+        let mut in_b = vec![false; 50_100];
+        for range in &a {
+            range.clone().for_each(|value| in_a[value as usize] = true);
+        }
+        for range in &b {
+            range.clone().for_each(|value| in_b[value as usize] = true);
+        }
+        let count = |f: &dyn Fn(usize) -> bool| (0..50_100).filter(|&i| f(i)).count() as u128;
+
+        let mut inserted = IntervalSet::new();
+        for range in &a {
+            inserted.insert(range.clone());
+        }
+        let a: IntervalSet = a.into_iter().collect();
+        assert_eq!(inserted, a);
+        let b: IntervalSet = b.into_iter().collect();
+
+        assert_eq!(a.len(), count(&|i| in_a[i]));
+        assert_eq!(a.union(&b).len(), count(&|i| in_a[i] || in_b[i]));
+        assert_eq!(a.intersection(&b).len(), count(&|i| in_a[i] && in_b[i]));
+        assert_eq!(a.difference(&b).len(), count(&|i| in_a[i] && !in_b[i]));
+    }
+}
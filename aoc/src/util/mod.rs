@@ -0,0 +1,10 @@
+//! Helpers shared by the solutions of every year.
+
+pub mod cycle;
+pub mod grid;
+pub mod input;
+pub mod interval;
+pub mod ocr;
+pub mod parse;
+pub mod search;
+pub mod voxel;
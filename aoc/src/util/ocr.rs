@@ -0,0 +1,190 @@
+//! Reading the block capitals that some puzzles draw as their answer, in the
+//! font of 4x6 glyphs with a blank column between letters.
+
+use super::grid::{Grid, Point};
+
+const WIDTH: usize = 4;
+const HEIGHT: usize = 6;
+/// Each letter plus the blank column after it.
+const ADVANCE: usize = WIDTH + 1;
+
+/// Every known letter, drawn row by row with `#` for lit pixels.
+const GLYPHS: [(char, &str); 18] = [
+    ('A', ".##.#..##..######..##..#"),
+    ('B', "###.#..####.#..##..####."),
+    ('C', ".##.#..##...#...#..#.##."),
+    ('E', "#####...###.#...#...####"),
+    ('F', "#####...###.#...#...#..."),
+    ('G', ".##.#..##...#.###..#.###"),
+    ('H', "#..##..######..##..##..#"),
+    ('I', ".###..#...#...#...#..###"),
+    ('J', "..##...#...#...##..#.##."),
+    ('K', "#..##.#.##..#.#.#.#.#..#"),
+    ('L', "#...#...#...#...#...####"),
+    ('O', ".##.#..##..##..##..#.##."),
+    ('P', "###.#..##..####.#...#..."),
+    ('R', "###.#..##..####.#.#.#..#"),
+    ('S', ".####...#....##....####."),
+    ('U', "#..##..##..##..##..#.##."),
+    ('Y', "#...#....#.#..#...#...#."),
+    ('Z', "####...#..#..#..#...####"),
+];
+
+/// Read the letters on a screen of lit pixels, starting from the left edge.
+pub fn read(screen: &Grid<bool>) -> anyhow::Result<String> {
+    if screen.height() != HEIGHT {
+        anyhow::bail!(
+            "letters are {HEIGHT} pixels high, the screen is {}",
+            screen.height()
+        );
+    }
+
+    (0..screen.width().div_ceil(ADVANCE))
+        .map(|index| {
+            let left = index * ADVANCE;
+            let pixels: String = (0..HEIGHT)
+                .flat_map(|y| (left..left + WIDTH).map(move |x| Point::new(x, y)))
+                .map(|point| match screen.get(point) {
+                    Some(true) => '#',
+                    _ => '.',
+                })
+                .collect();
+
+            GLYPHS
+                .iter()
+                .find(|(_, glyph)| *glyph == pixels)
+                .map(|&(letter, _)| letter)
+                .ok_or_else(|| {
+                    anyhow::anyhow!("letter {} is not one I know:\n{}", index + 1, draw(&pixels))
+                })
+        })
+        .collect()
+}
+
+/// Lay out a glyph's pixels as rows.
+fn draw(pixels: &str) -> String {
+    let rows: Vec<_> = pixels
+        .as_bytes()
+        .chunks(WIDTH)
+        .map(|row| std::str::from_utf8(row).expect("pixels are ASCII"))
+        .collect();
+    rows.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn screen(drawing: &str) -> Grid<bool> {
+        Grid::parse(drawing, |c| Ok(c == '#')).unwrap()
+    }
+
+    #[test]
+    fn reads_letters() {
+        let drawing = "\
+###..####.#..#.####..##..###..####.####.
+#..#.#....#.#.....#.#..#.#..#.#....#....
+#..#.###..##.....#..#....#..#.###..###..
+###..#....#.#...#...#....###..#....#....
+#.#..#....#.#..#....#..#.#....#....#....
+#..#.####.#..#.####..##..#....####.#....";
+
+        let actual = read(&screen(drawing)).unwrap();
+
+        assert_eq!(actual, "REKZCPEF");
+    }
+
+    /// Screens as the puzzles draw them, written out by hand rather than
+    /// built from [`GLYPHS`]. The first four are puzzle answers; the last
+    /// makes up the letters they miss, with `Y` a column wider than the rest
+    /// as the puzzles draw it.
+    const SCREENS: [(&str, &str); 5] = [
+        (
+            "BJFRHRFU",
+            "\
+###....##.####.###..#..#.###..####.#..#.
+#..#....#.#....#..#.#..#.#..#.#....#..#.
+###.....#.###..#..#.####.#..#.###..#..#.
+#..#....#.#....###..#..#.###..#....#..#.
+#..#.#..#.#....#.#..#..#.#.#..#....#..#.
+###...##..#....#..#.#..#.#..#.#.....##..",
+        ),
+        (
+            "PGHFGLUG",
+            "\
+###...##..#..#.####..##..#....#..#..##..
+#..#.#..#.#..#.#....#..#.#....#..#.#..#.
+#..#.#....####.###..#....#....#..#.#....
+###..#.##.#..#.#....#.##.#....#..#.#.##.
+#....#..#.#..#.#....#..#.#....#..#.#..#.
+#.....###.#..#.#.....###.####..##...###.",
+        ),
+        (
+            "ZCBAJFJZ",
+            "\
+####..##..###...##....##.####...##.####.
+...#.#..#.#..#.#..#....#.#.......#....#.
+..#..#....###..#..#....#.###.....#...#..
+.#...#....#..#.####....#.#.......#..#...
+#....#..#.#..#.#..#.#..#.#....#..#.#....
+####..##..###..#..#..##..#.....##..####.",
+        ),
+        (
+            "EKRHEPUZ",
+            "\
+####.#..#.###..#..#.####.###..#..#.####.
+#....#.#..#..#.#..#.#....#..#.#..#....#.
+###..##...#..#.####.###..#..#.#..#...#..
+#....#.#..###..#..#.#....###..#..#..#...
+#....#.#..#.#..#..#.#....#....#..#.#....
+####.#..#.#..#.#..#.####.#.....##..####.",
+        ),
+        (
+            "IOSYACEL",
+            "\
+.###..##...###.#...#.##...##..####.#....
+..#..#..#.#....#...##..#.#..#.#....#....
+..#..#..#.#.....#.#.#..#.#....###..#....
+..#..#..#..##....#..####.#....#....#....
+..#..#..#....#...#..#..#.#..#.#....#....
+.###..##..###....#..#..#..##..####.####.",
+        ),
+    ];
+
+    #[test]
+    fn reads_every_letter() {
+        for (expected, drawing) in SCREENS {
+            assert_eq!(read(&screen(drawing)).unwrap(), expected);
+        }
+
+        for (letter, _) in GLYPHS {
+            assert!(
+                SCREENS.iter().any(|(text, _)| text.contains(letter)),
+                "no screen has {letter}"
+            );
+        }
+    }
+
+    #[test]
+    fn unknown_letter() {
+        let drawing = "\
+#..#.####
+#..#.#..#
+####.#..#
+#..#.#..#
+#..#.#..#
+#..#.####";
+
+        let actual = read(&screen(drawing)).unwrap_err().to_string();
+
+        assert_eq!(
+            actual,
+            "letter 2 is not one I know:\n####\n#..#\n#..#\n#..#\n#..#\n####"
+        );
+    }
+
+    #[test]
+    fn wrong_height() {
+        assert!(read(&screen("#..#\n#..#")).is_err());
+    }
+}
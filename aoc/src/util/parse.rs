@@ -0,0 +1,441 @@
+//! A small set of parser combinators for puzzle inputs.
+//!
+//! A parser takes the remaining input and either returns a value along with
+//! what is left, or an [`Error`] saying what it expected. Errors remember how
+//! much input was left, so [`parse_all`] can turn them into a line and column.
+
+use std::{fmt, str::FromStr};
+
+pub type Result<'a, T> = std::result::Result<(T, &'a str), Error>;
+
+/// A failure somewhere inside the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    expected: String,
+    remaining: usize,
+}
+
+impl Error {
+    /// Expected `expected` at the start of `input`.
+    pub fn new(expected: impl Into<String>, input: &str) -> Self {
+        Self {
+            expected: expected.into(),
+            remaining: input.len(),
+        }
+    }
+
+    /// Keep whichever error got furthest into the input, or both if they got
+    /// equally far.
+    fn or(self, other: Self) -> Self {
+        use std::cmp::Ordering::*;
+        match self.remaining.cmp(&other.remaining) {
+            Less => self,
+            Greater => other,
+            Equal => Self {
+                expected: format!("{} or {}", self.expected, other.expected),
+                remaining: self.remaining,
+            },
+        }
+    }
+}
+
+pub trait Parser<'a, T> {
+    fn parse(&self, input: &'a str) -> Result<'a, T>;
+}
+
+impl<'a, T, F> Parser<'a, T> for F
+where
+    F: Fn(&'a str) -> Result<'a, T>,
+{
+    fn parse(&self, input: &'a str) -> Result<'a, T> {
+        self(input)
+    }
+}
+
+/// Exactly the text `expected`.
+pub fn literal<'a>(expected: &'static str) -> impl Parser<'a, &'a str> {
+    move |input: &'a str| match input.strip_prefix(expected) {
+        Some(rest) => Ok((&input[..expected.len()], rest)),
+        None => Err(Error::new(format!("{expected:?}"), input)),
+    }
+}
+
+/// A single character, whatever it is.
+pub fn any_char<'a>() -> impl Parser<'a, char> {
+    move |input: &'a str| {
+        let mut chars = input.chars();
+        match chars.next() {
+            Some(c) => Ok((c, chars.as_str())),
+            None => Err(Error::new("a character", input)),
+        }
+    }
+}
+
+/// The longest run, possibly empty, of characters matching `predicate`.
+pub fn take_while<'a>(predicate: impl Fn(char) -> bool) -> impl Parser<'a, &'a str> {
+    move |input: &'a str| {
+        let end = input.find(|c| !predicate(c)).unwrap_or(input.len());
+        Ok(input.split_at(end))
+    }
+}
+
+/// Any number of spaces and tabs, including none. Newlines are left alone,
+/// as [`lines`] and [`blocks`] rely on them.
+pub fn whitespace<'a>() -> impl Parser<'a, &'a str> {
+    take_while(|c| c == ' ' || c == '\t')
+}
+
+/// A decimal number, with a leading `-` if `N` allows it.
+pub fn number<'a, N>() -> impl Parser<'a, N>
+where
+    N: FromStr,
+    N::Err: fmt::Display,
+{
+    move |input: &'a str| {
+        let sign = usize::from(input.starts_with('-'));
+        let digits = input[sign..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(input.len() - sign);
+        if digits == 0 {
+            return Err(Error::new("a number", input));
+        }
+
+        let (number, rest) = input.split_at(sign + digits);
+        match number.parse() {
+            Ok(number) => Ok((number, rest)),
+            Err(error) => Err(Error::new(format!("a number ({error})"), input)),
+        }
+    }
+}
+
+pub fn map<'a, T, U>(parser: impl Parser<'a, T>, f: impl Fn(T) -> U) -> impl Parser<'a, U> {
+    move |input: &'a str| {
+        let (value, rest) = parser.parse(input)?;
+        Ok((f(value), rest))
+    }
+}
+
+/// Like [`map`], but `f` can reject the value, in which case the error points
+/// at where `parser` started.
+pub fn try_map<'a, T, U, E>(
+    parser: impl Parser<'a, T>,
+    f: impl Fn(T) -> std::result::Result<U, E>,
+) -> impl Parser<'a, U>
+where
+    E: fmt::Display,
+{
+    move |input: &'a str| {
+        let (value, rest) = parser.parse(input)?;
+        match f(value) {
+            Ok(value) => Ok((value, rest)),
+            Err(error) => Err(Error::new(error.to_string(), input)),
+        }
+    }
+}
+
+pub fn pair<'a, A, B>(
+    first: impl Parser<'a, A>,
+    second: impl Parser<'a, B>,
+) -> impl Parser<'a, (A, B)> {
+    move |input: &'a str| {
+        let (a, rest) = first.parse(input)?;
+        let (b, rest) = second.parse(rest)?;
+        Ok(((a, b), rest))
+    }
+}
+
+/// `parser`, after something that is thrown away.
+pub fn preceded<'a, A, T>(
+    prefix: impl Parser<'a, A>,
+    parser: impl Parser<'a, T>,
+) -> impl Parser<'a, T> {
+    map(pair(prefix, parser), |(_, value)| value)
+}
+
+/// `parser`, followed by something that is thrown away.
+pub fn terminated<'a, T, B>(
+    parser: impl Parser<'a, T>,
+    suffix: impl Parser<'a, B>,
+) -> impl Parser<'a, T> {
+    map(pair(parser, suffix), |(value, _)| value)
+}
+
+/// `parser`, between two things that are thrown away.
+pub fn delimited<'a, A, T, B>(
+    prefix: impl Parser<'a, A>,
+    parser: impl Parser<'a, T>,
+    suffix: impl Parser<'a, B>,
+) -> impl Parser<'a, T> {
+    preceded(prefix, terminated(parser, suffix))
+}
+
+/// `parser` if it matches, otherwise nothing, consuming no input.
+pub fn optional<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Option<T>> {
+    move |input: &'a str| match parser.parse(input) {
+        Ok((value, rest)) => Ok((Some(value), rest)),
+        Err(_) => Ok((None, input)),
+    }
+}
+
+/// Zero or more of `parser`, with `separator` between each. Once a separator
+/// has matched, the next item must too.
+pub fn separated<'a, T, S>(
+    parser: impl Parser<'a, T>,
+    separator: impl Parser<'a, S>,
+) -> impl Parser<'a, Vec<T>> {
+    move |input: &'a str| {
+        let mut items = Vec::new();
+        let Ok((item, mut rest)) = parser.parse(input) else {
+            return Ok((items, input));
+        };
+        items.push(item);
+
+        while let Ok((_, after)) = separator.parse(rest) {
+            let (item, after) = parser.parse(after)?;
+            items.push(item);
+            rest = after;
+        }
+
+        Ok((items, rest))
+    }
+}
+
+/// One `parser` per line, allowing a trailing newline at the end of the
+/// input.
+pub fn lines<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    split_on("\n", parser)
+}
+
+/// One `parser` per block of lines, with blocks separated by a blank line.
+pub fn blocks<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    split_on("\n\n", parser)
+}
+
+fn split_on<'a, T>(separator: &'static str, parser: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |input: &'a str| {
+        let mut items = Vec::new();
+        let mut rest = input;
+        while !rest.is_empty() {
+            let (item, after) = parser.parse(rest)?;
+            items.push(item);
+            rest = after;
+
+            // Stop before a separator that is followed by nothing or by a
+            // blank line, so blocks of lines can be nested.
+            match after.strip_prefix(separator) {
+                Some(next) if !next.is_empty() && !next.starts_with('\n') => rest = next,
+                _ => break,
+            }
+        }
+
+        if rest.chars().all(|c| c == '\n') {
+            rest = "";
+        }
+        Ok((items, rest))
+    }
+}
+
+/// Parsers to try in order, taking the first that matches.
+pub trait Alt<'a, T> {
+    fn choose(&self, input: &'a str) -> Result<'a, T>;
+}
+
+macro_rules! impl_alt {
+    ($($parser:ident $index:tt),+) => {
+        impl<'a, T, $($parser: Parser<'a, T>),+> Alt<'a, T> for ($($parser,)+) {
+            fn choose(&self, input: &'a str) -> Result<'a, T> {
+                let mut error: Option<Error> = None;
+                $(
+                    match self.$index.parse(input) {
+                        Ok(ok) => return Ok(ok),
+                        Err(next) => {
+                            error = Some(match error {
+                                Some(error) => error.or(next),
+                                None => next,
+                            })
+                        }
+                    }
+                )+
+                Err(error.expect("at least one parser"))
+            }
+        }
+    };
+}
+
+impl_alt!(A 0, B 1);
+impl_alt!(A 0, B 1, C 2);
+impl_alt!(A 0, B 1, C 2, D 3);
+impl_alt!(A 0, B 1, C 2, D 3, E 4);
+
+/// The first of `parsers` that matches, e.g. `alt((literal("a"), literal("b")))`.
+pub fn alt<'a, T>(parsers: impl Alt<'a, T>) -> impl Parser<'a, T> {
+    move |input: &'a str| parsers.choose(input)
+}
+
+/// An [`Error`] placed at a line and column of the whole input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+}
+
+impl ParseError {
+    fn new(text: &str, error: Error) -> Self {
+        let offset = text.len() - error.remaining;
+        let before = &text[..offset];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+        let column = before[line_start..].chars().count() + 1;
+        let found = text[offset..]
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .to_string();
+
+        Self {
+            line,
+            column,
+            expected: error.expected,
+            found,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found ",
+            self.line, self.column, self.expected
+        )?;
+        if self.found.is_empty() {
+            write!(f, "end of line")
+        } else {
+            write!(f, "{:?}", self.found)
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Run `parser` over the whole of `text`, failing if anything is left over.
+pub fn parse_all<'a, T>(
+    parser: impl Parser<'a, T>,
+    text: &'a str,
+) -> std::result::Result<T, ParseError> {
+    match parser.parse(text) {
+        Ok((value, "")) => Ok(value),
+        Ok((_, rest)) => Err(ParseError::new(text, Error::new("end of input", rest))),
+        Err(error) => Err(ParseError::new(text, error)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_number() {
+        let actual: (u32, &str) = number().parse("123abc").unwrap();
+        let expected = (123, "abc");
+
+        assert_eq!(actual, expected);
+
+        let actual: (i32, &str) = number().parse("-12").unwrap();
+        let expected = (-12, "");
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn number_out_of_range() {
+        let actual = parse_all(number::<u8>(), "300");
+
+        assert!(actual.is_err());
+
+        let actual = parse_all(number::<u32>(), "-3");
+
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn parse_separated() {
+        let parser = separated(number::<u32>(), pair(literal(","), whitespace()));
+
+        let actual = parse_all(parser, "1, 2,3").unwrap();
+        let expected = vec![1, 2, 3];
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn parse_separated_empty() {
+        let parser = separated(number::<u32>(), literal(","));
+
+        let actual = parse_all(parser, "").unwrap();
+        let expected: Vec<u32> = vec![];
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn parse_lines_with_trailing_newline() {
+        let actual = parse_all(lines(number::<u32>()), "1\n2\n3\n").unwrap();
+        let expected = vec![1, 2, 3];
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn parse_blocks() {
+        let parser = blocks(lines(number::<u32>()));
+
+        let actual = parse_all(parser, "1\n2\n\n3\n").unwrap();
+        let expected = vec![vec![1, 2], vec![3]];
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn parse_alt() {
+        fn yes_or_no<'a>() -> impl Parser<'a, bool> {
+            alt((map(literal("yes"), |_| true), map(literal("no"), |_| false)))
+        }
+
+        assert_eq!(parse_all(yes_or_no(), "no"), Ok(false));
+
+        let actual = parse_all(yes_or_no(), "maybe").unwrap_err().expected;
+        let expected = r#""yes" or "no""#;
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn error_position() {
+        let parser = lines(separated(number::<u32>(), literal(",")));
+
+        let actual = parse_all(parser, "1,2\n3,x,5").unwrap_err();
+        let expected = ParseError {
+            line: 2,
+            column: 3,
+            expected: "a number".to_string(),
+            found: "x,5".to_string(),
+        };
+
+        assert_eq!(actual, expected);
+        assert_eq!(
+            actual.to_string(),
+            r#"line 2, column 3: expected a number, found "x,5""#
+        );
+    }
+
+    #[test]
+    fn leftover_input() {
+        let actual = parse_all(number::<u32>(), "12 ").unwrap_err();
+
+        assert_eq!(actual.column, 3);
+        assert_eq!(actual.expected, "end of input");
+    }
+}
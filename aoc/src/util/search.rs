@@ -0,0 +1,271 @@
+//! Breadth-first search, Dijkstra and A* over any hashable state, with the
+//! graph given as a successor function rather than built up front.
+
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// The cheapest way found to reach a goal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<S, C> {
+    pub cost: C,
+    /// Every state visited, from the start to the goal inclusive.
+    pub states: Vec<S>,
+}
+
+/// States seen so far, each with the cost of the best way found to reach it
+/// and the index of the state it was reached from.
+struct Visited<S, C> {
+    nodes: Vec<(S, C, Option<usize>)>,
+    index: HashMap<S, usize>,
+}
+
+impl<S: Clone + Eq + Hash, C: Copy> Visited<S, C> {
+    fn new(start: S, cost: C) -> Self {
+        Self {
+            nodes: vec![(start.clone(), cost, None)],
+            index: HashMap::from([(start, 0)]),
+        }
+    }
+
+    fn path(&self, mut node: usize) -> Path<S, C> {
+        let cost = self.nodes[node].1;
+        let mut states = vec![self.nodes[node].0.clone()];
+        while let Some(parent) = self.nodes[node].2 {
+            states.push(self.nodes[parent].0.clone());
+            node = parent;
+        }
+        states.reverse();
+
+        Path { cost, states }
+    }
+}
+
+/// Fewest steps from `start` to a state matching `is_goal`, where every
+/// step costs the same.
+pub fn bfs<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, usize>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut visited = Visited::new(start, 0);
+    let mut queue = VecDeque::from([0]);
+
+    while let Some(node) = queue.pop_front() {
+        let (state, steps, _) = &visited.nodes[node];
+        if is_goal(state) {
+            return Some(visited.path(node));
+        }
+
+        let steps = steps + 1;
+        for next in successors(state) {
+            if let Entry::Vacant(entry) = visited.index.entry(next) {
+                let next = visited.nodes.len();
+                visited.nodes.push((entry.key().clone(), steps, Some(node)));
+                entry.insert(next);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// Fewest steps from `start` to every state reachable from it.
+pub fn distances<S, I>(start: S, mut successors: impl FnMut(&S) -> I) -> HashMap<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((state, steps)) = queue.pop_front() {
+        for next in successors(&state) {
+            if let Entry::Vacant(entry) = distances.entry(next) {
+                queue.push_back((entry.key().clone(), steps + 1));
+                entry.insert(steps + 1);
+            }
+        }
+    }
+
+    distances
+}
+
+/// Cheapest path from `start` to a state matching `is_goal`, where
+/// `successors` gives each next state with the cost of stepping to it.
+pub fn dijkstra<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(start, successors, |_| C::default(), is_goal)
+}
+
+/// Like [`dijkstra`], but searching towards the goal first using `heuristic`,
+/// which must never overestimate the remaining cost for the path to be the
+/// cheapest.
+pub fn astar<S, C, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let estimate = heuristic(&start);
+    let mut visited = Visited::new(start, C::default());
+    let mut queue = BinaryHeap::from([Reverse((estimate, C::default(), 0))]);
+
+    while let Some(Reverse((_, cost, node))) = queue.pop() {
+        let (state, best, _) = &visited.nodes[node];
+        if cost > *best {
+            // A cheaper way here was found after this one was queued.
+            continue;
+        }
+        if is_goal(state) {
+            return Some(visited.path(node));
+        }
+
+        for (next, step) in successors(state) {
+            let cost = cost + step;
+            let next = match visited.index.entry(next) {
+                Entry::Occupied(entry) => {
+                    let next = *entry.get();
+                    if cost >= visited.nodes[next].1 {
+                        continue;
+                    }
+                    visited.nodes[next].1 = cost;
+                    visited.nodes[next].2 = Some(node);
+                    next
+                }
+                Entry::Vacant(entry) => {
+                    let next = visited.nodes.len();
+                    visited.nodes.push((entry.key().clone(), cost, Some(node)));
+                    entry.insert(next);
+                    next
+                }
+            };
+            let estimate = cost + heuristic(&visited.nodes[next].0);
+            queue.push(Reverse((estimate, cost, next)));
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::grid::{Grid, Point};
+
+    const MAZE: &str = "\
+S.#.....
+.##.###.
+....#E..
+.####.#.
+........";
+
+    fn maze() -> (Grid<char>, Point, Point) {
+        let grid = Grid::parse(MAZE, Ok).unwrap();
+        let start = grid.position(|&c| c == 'S').unwrap();
+        let end = grid.position(|&c| c == 'E').unwrap();
+
+        (grid, start, end)
+    }
+
+    fn open<'a>(grid: &'a Grid<char>, point: &Point) -> impl Iterator<Item = Point> + 'a {
+        grid.neighbours4(*point).filter(|&next| grid[next] != '#')
+    }
+
+    #[test]
+    fn bfs_on_grid() {
+        let (grid, start, end) = maze();
+
+        let path = bfs(start, |point| open(&grid, point), |&point| point == end).unwrap();
+
+        assert_eq!(path.cost, 11);
+        assert_eq!(path.states.len(), 12);
+        assert_eq!(path.states.first(), Some(&start));
+        assert_eq!(path.states.last(), Some(&end));
+        for pair in path.states.windows(2) {
+            assert!(grid.neighbours4(pair[0]).any(|next| next == pair[1]));
+        }
+    }
+
+    #[test]
+    fn bfs_start_is_goal() {
+        let actual = bfs(1, |&n| [n + 1], |&n| n == 1).unwrap();
+        let expected = Path {
+            cost: 0,
+            states: vec![1],
+        };
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn bfs_unreachable() {
+        let actual = bfs(1u32, |&n| (n < 10).then_some(n + 1), |&n| n == 0);
+
+        assert_eq!(actual, None);
+    }
+
+    #[test]
+    fn distances_on_grid() {
+        let (grid, start, end) = maze();
+
+        let actual = distances(start, |point| open(&grid, point));
+
+        assert_eq!(actual[&start], 0);
+        assert_eq!(actual[&end], 11);
+        assert_eq!(actual.len(), grid.iter().filter(|(_, &c)| c != '#').count());
+    }
+
+    #[test]
+    fn dijkstra_prefers_cheap_detour() {
+        // 1 -> 4 costs 10 directly, or 3 via 2 and 3.
+        let edges = HashMap::from([
+            (1, vec![(4, 10), (2, 1)]),
+            (2, vec![(3, 1)]),
+            (3, vec![(4, 1)]),
+            (4, vec![]),
+        ]);
+
+        let actual = dijkstra(1, |n| edges[n].clone(), |&n| n == 4).unwrap();
+        let expected = Path {
+            cost: 3,
+            states: vec![1, 2, 3, 4],
+        };
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn astar_matches_dijkstra() {
+        let (grid, start, end) = maze();
+        let successors = |point: &Point| open(&grid, point).map(|next| (next, 1));
+
+        let manhattan = |point: &Point| point.x.abs_diff(end.x) + point.y.abs_diff(end.y);
+        let astar = astar(start, successors, manhattan, |&point| point == end).unwrap();
+        let dijkstra = dijkstra(start, successors, |&point| point == end).unwrap();
+
+        assert_eq!(astar.cost, 11);
+        assert_eq!(astar.cost, dijkstra.cost);
+    }
+}
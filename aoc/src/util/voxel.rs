@@ -0,0 +1,179 @@
+//! Points in 3D space and sparse sets of unit cubes, the 3D counterpart to
+//! [`grid`](super::grid).
+
+use std::{collections::HashSet, fmt};
+
+/// A position in 3D space, which unlike a grid point may be negative.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Point3 {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl Point3 {
+    pub fn new(x: i32, y: i32, z: i32) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn offset(self, (dx, dy, dz): (i32, i32, i32)) -> Self {
+        Self::new(self.x + dx, self.y + dy, self.z + dz)
+    }
+
+    /// The 6 points sharing a face with this one.
+    pub fn neighbours6(self) -> impl Iterator<Item = Self> {
+        FACES.into_iter().map(move |step| self.offset(step))
+    }
+
+    /// The smallest of each coordinate.
+    pub fn min(self, other: Self) -> Self {
+        Self::new(
+            self.x.min(other.x),
+            self.y.min(other.y),
+            self.z.min(other.z),
+        )
+    }
+
+    /// The largest of each coordinate.
+    pub fn max(self, other: Self) -> Self {
+        Self::new(
+            self.x.max(other.x),
+            self.y.max(other.y),
+            self.z.max(other.z),
+        )
+    }
+}
+
+impl fmt::Display for Point3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+/// Steps to the 6 neighbours sharing a face: left, right, down, up, back
+/// and front.
+pub const FACES: [(i32, i32, i32); 6] = [
+    (-1, 0, 0),
+    (1, 0, 0),
+    (0, -1, 0),
+    (0, 1, 0),
+    (0, 0, -1),
+    (0, 0, 1),
+];
+
+/// Unit cubes, each named by the point at its corner, along with the box
+/// that holds them all.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VoxelSet {
+    voxels: HashSet<Point3>,
+    bounds: Option<(Point3, Point3)>,
+}
+
+impl VoxelSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a voxel, returning whether it was new.
+    pub fn insert(&mut self, voxel: Point3) -> bool {
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (min.min(voxel), max.max(voxel)),
+            None => (voxel, voxel),
+        });
+        self.voxels.insert(voxel)
+    }
+
+    pub fn contains(&self, voxel: Point3) -> bool {
+        self.voxels.contains(&voxel)
+    }
+
+    pub fn len(&self) -> usize {
+        self.voxels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.voxels.is_empty()
+    }
+
+    /// Every voxel, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = Point3> + '_ {
+        self.voxels.iter().copied()
+    }
+
+    /// The lowest and highest corner of the smallest box holding every
+    /// voxel, or `None` if there are none.
+    pub fn bounds(&self) -> Option<(Point3, Point3)> {
+        self.bounds
+    }
+
+    /// The neighbours of `voxel` sharing a face with it that are in the set.
+    pub fn neighbours6(&self, voxel: Point3) -> impl Iterator<Item = Point3> + '_ {
+        voxel
+            .neighbours6()
+            .filter(|&neighbour| self.contains(neighbour))
+    }
+}
+
+impl FromIterator<Point3> for VoxelSet {
+    fn from_iter<I: IntoIterator<Item = Point3>>(voxels: I) -> Self {
+        let mut set = Self::new();
+        for voxel in voxels {
+            set.insert(voxel);
+        }
+
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neighbours_of_a_point() {
+        let mut actual: Vec<_> = Point3::new(0, 5, -2).neighbours6().collect();
+        actual.sort();
+
+        assert_eq!(
+            actual,
+            [
+                Point3::new(-1, 5, -2),
+                Point3::new(0, 4, -2),
+                Point3::new(0, 5, -3),
+                Point3::new(0, 5, -1),
+                Point3::new(0, 6, -2),
+                Point3::new(1, 5, -2),
+            ]
+        );
+    }
+
+    #[test]
+    fn neighbours_in_a_set() {
+        let set: VoxelSet = [(0, 0, 0), (1, 0, 0), (1, 1, 0), (0, 0, 2)]
+            .into_iter()
+            .map(|(x, y, z)| Point3::new(x, y, z))
+            .collect();
+
+        let mut actual: Vec<_> = set.neighbours6(Point3::new(1, 0, 0)).collect();
+        actual.sort();
+
+        assert_eq!(actual, [Point3::new(0, 0, 0), Point3::new(1, 1, 0)]);
+        assert_eq!(set.neighbours6(Point3::new(0, 0, 2)).count(), 0);
+    }
+
+    #[test]
+    fn bounds_and_duplicates() {
+        let mut set = VoxelSet::new();
+        assert_eq!(set.bounds(), None);
+
+        assert!(set.insert(Point3::new(2, -1, 4)));
+        assert!(set.insert(Point3::new(-3, 0, 1)));
+        assert!(!set.insert(Point3::new(2, -1, 4)));
+
+        assert_eq!(set.len(), 2);
+        assert_eq!(
+            set.bounds(),
+            Some((Point3::new(-3, -1, 1), Point3::new(2, 0, 4)))
+        );
+    }
+}
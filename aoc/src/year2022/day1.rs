@@ -0,0 +1,108 @@
+use crate::{solution::Solution, trace};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<usize>;
+
+    fn parse(file: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse_elves(file))
+    }
+
+    fn part1(elves: &Self::Input) -> anyhow::Result<String> {
+        Ok(max_elf(elves, 1).to_string())
+    }
+
+    fn part2(elves: &Self::Input) -> anyhow::Result<String> {
+        Ok(max_elf(elves, 3).to_string())
+    }
+}
+
+fn parse_elves(file: &str) -> Vec<usize> {
+    // for line in file.split('\n') {
+    //     if line.is_empty() {
+    //         elves.push(elf);
+
+    //         elf = 0;
+    //     } else {
+    //         let calories: usize = line.parse().unwrap();
+    //         elf += calories;
+    //     }
+    // }
+    //
+    // elves.push(elf);
+
+    file.split("\n\n")
+        .enumerate()
+        .map(|(index, elf)| {
+            let total = elf
+                .split('\n')
+                .fold(0, |sum, food| sum + food.parse::<usize>().unwrap_or(0));
+            trace!("elf", elf = index + 1, total = total);
+            total
+        })
+        .collect()
+}
+
+fn max_elf(elves: &[usize], n_max: usize) -> usize {
+    let mut elves = elves.to_vec();
+    elves.sort();
+    elves.reverse();
+
+    elves.iter().take(n_max).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_file() {
+        let actual = max_elf(&parse_elves(""), 1);
+        let expected = 0;
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn one_snack() {
+        let actual = max_elf(&parse_elves("5600"), 1);
+        let expected = 5600;
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn few_snacks() {
+        let actual = max_elf(&parse_elves("5600\n200\n400"), 1);
+        let expected = 6200;
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn couple_elves() {
+        let actual = max_elf(&parse_elves("5600\n200\n400\n\n10000"), 1);
+        let expected = 10000;
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn couple_elves_top_2() {
+        let actual = max_elf(&parse_elves("5600\n200\n400\n\n10000"), 2);
+        let expected = 16200;
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn traces_elf_totals() {
+        let (_, events) = crate::trace::collect(|| parse_elves("100\n200\n\n50"));
+
+        let actual: Vec<_> = events.iter().map(|event| event.to_string()).collect();
+        let expected = vec!["elf elf=1 total=300", "elf elf=2 total=50"];
+
+        assert_eq!(actual, expected);
+    }
+}
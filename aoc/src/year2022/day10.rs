@@ -0,0 +1,320 @@
+use crate::{
+    solution::Solution,
+    trace,
+    util::{
+        grid::{Grid, Point},
+        ocr,
+        parse::{self, alt, lines, literal, map, number, preceded, Parser},
+    },
+};
+
+const SCREEN_WIDTH: usize = 40;
+const SCREEN_HEIGHT: usize = 6;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<Instruction>;
+
+    fn parse(file: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse::parse_all(lines(instruction()), file)?)
+    }
+
+    fn part1(program: &Self::Input) -> anyhow::Result<String> {
+        let total: i64 = Cycles::new(program)
+            .filter(|&(cycle, _)| cycle % 40 == 20 && cycle <= 220)
+            .map(|(cycle, x)| {
+                let strength = cycle as i64 * x as i64;
+                trace!("signal", cycle = cycle, x = x, strength = strength);
+                strength
+            })
+            .sum();
+
+        Ok(total.to_string())
+    }
+
+    fn part2(program: &Self::Input) -> anyhow::Result<String> {
+        let screen = render(program);
+        trace!(
+            "screen",
+            image = screen.map(|&lit| if lit { '#' } else { '.' }).to_string()
+        );
+
+        ocr::read(&screen)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    /// Takes one cycle and does nothing.
+    Noop,
+    /// Takes two cycles, then adds to `X`.
+    Addx(i32),
+}
+
+/// The value of `X` during each cycle of running a program, numbered from
+/// 1. An instruction's effect shows from the cycle after it finishes.
+pub struct Cycles<'a> {
+    program: std::slice::Iter<'a, Instruction>,
+    cycle: usize,
+    x: i32,
+    /// An `addx` that has run for one of its two cycles.
+    pending: Option<i32>,
+}
+
+impl<'a> Cycles<'a> {
+    pub fn new(program: &'a [Instruction]) -> Self {
+        Self {
+            program: program.iter(),
+            cycle: 0,
+            x: 1,
+            pending: None,
+        }
+    }
+}
+
+impl Iterator for Cycles<'_> {
+    type Item = (usize, i32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let during = self.x;
+        match self.pending.take() {
+            Some(value) => self.x += value,
+            None => match self.program.next()? {
+                Instruction::Noop => {}
+                Instruction::Addx(value) => self.pending = Some(*value),
+            },
+        }
+        self.cycle += 1;
+
+        Some((self.cycle, during))
+    }
+}
+
+/// Draw one pixel per cycle, left to right and top to bottom, lit when the
+/// 3 pixel wide sprite centred on `X` covers it.
+pub fn render(program: &[Instruction]) -> Grid<bool> {
+    let mut screen = Grid::new(SCREEN_WIDTH, SCREEN_HEIGHT, false);
+
+    for (cycle, x) in Cycles::new(program).take(SCREEN_WIDTH * SCREEN_HEIGHT) {
+        let pixel = cycle - 1;
+        let column = pixel % SCREEN_WIDTH;
+        screen[Point::new(column, pixel / SCREEN_WIDTH)] = (column as i64 - x as i64).abs() <= 1;
+    }
+
+    screen
+}
+
+fn instruction<'a>() -> impl Parser<'a, Instruction> {
+    alt((
+        map(literal("noop"), |_| Instruction::Noop),
+        map(preceded(literal("addx "), number()), Instruction::Addx),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SMALL_EXAMPLE: &str = "noop
+addx 3
+addx -5
+";
+
+    const EXAMPLE: &str = "addx 15
+addx -11
+addx 6
+addx -3
+addx 5
+addx -1
+addx -8
+addx 13
+addx 4
+noop
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx -35
+addx 1
+addx 24
+addx -19
+addx 1
+addx 16
+addx -11
+noop
+noop
+addx 21
+addx -15
+noop
+noop
+addx -3
+addx 9
+addx 1
+addx -3
+addx 8
+addx 1
+addx 5
+noop
+noop
+noop
+noop
+noop
+addx -36
+noop
+addx 1
+addx 7
+noop
+noop
+noop
+addx 2
+addx 6
+noop
+noop
+noop
+noop
+noop
+addx 1
+noop
+noop
+addx 7
+addx 1
+noop
+addx -13
+addx 13
+addx 7
+noop
+addx 1
+addx -33
+noop
+noop
+noop
+addx 2
+noop
+noop
+noop
+addx 8
+noop
+addx -1
+addx 2
+addx 1
+noop
+addx 17
+addx -9
+addx 1
+addx 1
+addx -3
+addx 11
+noop
+noop
+addx 1
+noop
+addx 1
+noop
+noop
+addx -13
+addx -19
+addx 1
+addx 3
+addx 26
+addx -30
+addx 12
+addx -1
+addx 3
+addx 1
+noop
+noop
+noop
+addx -9
+addx 18
+addx 1
+addx 2
+noop
+noop
+addx 9
+noop
+noop
+noop
+addx -1
+addx 2
+addx -37
+addx 1
+addx 3
+noop
+addx 15
+addx -21
+addx 22
+addx -6
+addx 1
+noop
+addx 2
+addx 1
+noop
+addx -10
+noop
+noop
+addx 20
+addx 1
+addx 2
+addx 2
+addx -6
+addx -11
+noop
+noop
+noop
+";
+
+    #[test]
+    fn register_history() {
+        let program = Day10::parse(SMALL_EXAMPLE).unwrap();
+
+        let actual: Vec<_> = Cycles::new(&program).collect();
+        let expected = vec![(1, 1), (2, 1), (3, 1), (4, 4), (5, 4)];
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn signal_strength() {
+        let program = Day10::parse(EXAMPLE).unwrap();
+
+        let during: Vec<_> = Cycles::new(&program)
+            .filter(|&(cycle, _)| cycle % 40 == 20)
+            .map(|(_, x)| x)
+            .collect();
+        assert_eq!(during, vec![21, 19, 18, 21, 16, 18]);
+        assert_eq!(Day10::part1(&program).unwrap(), "13140");
+    }
+
+    #[test]
+    fn crt_image() {
+        let program = Day10::parse(EXAMPLE).unwrap();
+
+        let actual = render(&program)
+            .map(|&lit| if lit { '#' } else { '.' })
+            .to_string();
+        let expected = "\
+##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....";
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn example_is_not_letters() {
+        let program = Day10::parse(EXAMPLE).unwrap();
+
+        let actual = Day10::part2(&program).unwrap_err().to_string();
+
+        assert!(actual.starts_with("letter 1 is not one I know"), "{actual}");
+    }
+}
@@ -0,0 +1,340 @@
+use std::fmt;
+
+use crate::{
+    solution::Solution,
+    trace,
+    util::parse::{
+        self, alt, blocks, delimited, literal, map, number, pair, preceded, separated, Parser,
+    },
+};
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Vec<Monkey>;
+
+    fn parse(file: &str) -> anyhow::Result<Self::Input> {
+        let monkeys = parse::parse_all(blocks(monkey()), file)?;
+        check(&monkeys)?;
+
+        Ok(monkeys)
+    }
+
+    fn part1(monkeys: &Self::Input) -> anyhow::Result<String> {
+        Ok(monkey_business(monkeys, 20, true)?.to_string())
+    }
+
+    fn part2(monkeys: &Self::Input) -> anyhow::Result<String> {
+        Ok(monkey_business(monkeys, 10_000, false)?.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+    Old,
+    Number(u64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Multiply,
+}
+
+/// `new = {left} {operator} {right}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Operation {
+    left: Operand,
+    operator: Operator,
+    right: Operand,
+}
+
+impl Operation {
+    /// The new worry level, or `None` if it does not fit.
+    fn apply(self, old: u64) -> Option<u64> {
+        let value = |operand| match operand {
+            Operand::Old => old,
+            Operand::Number(number) => number,
+        };
+        let (left, right) = (value(self.left), value(self.right));
+
+        match self.operator {
+            Operator::Add => left.checked_add(right),
+            Operator::Multiply => left.checked_mul(right),
+        }
+    }
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let operand = |operand| match operand {
+            Operand::Old => "old".to_string(),
+            Operand::Number(number) => number.to_string(),
+        };
+        let operator = match self.operator {
+            Operator::Add => '+',
+            Operator::Multiply => '*',
+        };
+        write!(
+            f,
+            "new = {} {operator} {}",
+            operand(self.left),
+            operand(self.right)
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Monkey {
+    id: usize,
+    items: Vec<u64>,
+    operation: Operation,
+    divisor: u64,
+    if_true: usize,
+    if_false: usize,
+}
+
+impl Monkey {
+    fn target(&self, worry: u64) -> usize {
+        if worry.is_multiple_of(self.divisor) {
+            self.if_true
+        } else {
+            self.if_false
+        }
+    }
+}
+
+/// Make sure the monkeys are numbered in order and only throw to each other.
+fn check(monkeys: &[Monkey]) -> anyhow::Result<()> {
+    for (index, monkey) in monkeys.iter().enumerate() {
+        if monkey.id != index {
+            anyhow::bail!("monkey {} is listed in place {index}", monkey.id);
+        }
+        if monkey.divisor == 0 {
+            anyhow::bail!("monkey {index} tests for divisibility by 0");
+        }
+        for target in [monkey.if_true, monkey.if_false] {
+            if target >= monkeys.len() {
+                anyhow::bail!("monkey {index} throws to monkey {target}, which does not exist");
+            }
+            if target == index {
+                anyhow::bail!("monkey {index} throws to itself");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// How many items each monkey inspects over `rounds` rounds.
+///
+/// With `relief`, worry is divided by 3 after each inspection. Without it,
+/// worry is kept modulo the product of every monkey's divisor instead, which
+/// leaves every divisibility test with the same answer.
+pub fn inspections(monkeys: &[Monkey], rounds: usize, relief: bool) -> anyhow::Result<Vec<u64>> {
+    let modulus = monkeys
+        .iter()
+        .try_fold(1u64, |product, monkey| product.checked_mul(monkey.divisor))
+        .ok_or_else(|| anyhow::anyhow!("the product of the divisors does not fit in 64 bits"))?;
+
+    let mut items: Vec<Vec<u64>> = monkeys.iter().map(|monkey| monkey.items.clone()).collect();
+    let mut inspected = vec![0; monkeys.len()];
+
+    for round in 1..=rounds {
+        for (index, monkey) in monkeys.iter().enumerate() {
+            for old in std::mem::take(&mut items[index]) {
+                let Some(worry) = monkey.operation.apply(old) else {
+                    anyhow::bail!(
+                        "round {round}: monkey {index} overflowed {} with old = {old}",
+                        monkey.operation
+                    );
+                };
+                let worry = if relief { worry / 3 } else { worry % modulus };
+
+                items[monkey.target(worry)].push(worry);
+                inspected[index] += 1;
+            }
+        }
+
+        if round == 1 || round == 20 || round % 1000 == 0 {
+            trace!("round", round = round, inspected = inspected.clone());
+        }
+    }
+
+    Ok(inspected)
+}
+
+/// The product of the two largest numbers of inspections.
+pub fn monkey_business(monkeys: &[Monkey], rounds: usize, relief: bool) -> anyhow::Result<u64> {
+    let mut inspected = inspections(monkeys, rounds, relief)?;
+    inspected.sort_unstable_by(|a, b| b.cmp(a));
+
+    match inspected[..] {
+        [first, second, ..] => first
+            .checked_mul(second)
+            .ok_or_else(|| anyhow::anyhow!("monkey business of {first} * {second} overflowed")),
+        _ => anyhow::bail!("monkey business needs at least two monkeys"),
+    }
+}
+
+fn operand<'a>() -> impl Parser<'a, Operand> {
+    alt((
+        map(literal("old"), |_| Operand::Old),
+        map(number(), Operand::Number),
+    ))
+}
+
+fn operation<'a>() -> impl Parser<'a, Operation> {
+    let operator = alt((
+        map(literal(" + "), |_| Operator::Add),
+        map(literal(" * "), |_| Operator::Multiply),
+    ));
+
+    map(
+        pair(operand(), pair(operator, operand())),
+        |(left, (operator, right))| Operation {
+            left,
+            operator,
+            right,
+        },
+    )
+}
+
+fn monkey<'a>() -> impl Parser<'a, Monkey> {
+    let id = delimited(literal("Monkey "), number(), literal(":\n"));
+    let items = delimited(
+        literal("  Starting items: "),
+        separated(number(), literal(", ")),
+        literal("\n"),
+    );
+    let operation = delimited(literal("  Operation: new = "), operation(), literal("\n"));
+    let divisor = delimited(literal("  Test: divisible by "), number(), literal("\n"));
+    let if_true = delimited(
+        literal("    If true: throw to monkey "),
+        number(),
+        literal("\n"),
+    );
+    let if_false = preceded(literal("    If false: throw to monkey "), number());
+
+    map(
+        pair(
+            pair(id, pair(items, operation)),
+            pair(divisor, pair(if_true, if_false)),
+        ),
+        |((id, (items, operation)), (divisor, (if_true, if_false)))| Monkey {
+            id,
+            items,
+            operation,
+            divisor,
+            if_true,
+            if_false,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1
+";
+
+    #[test]
+    fn parse_monkey() {
+        let monkeys = Day11::parse(EXAMPLE).unwrap();
+
+        let expected = Monkey {
+            id: 2,
+            items: vec![79, 60, 97],
+            operation: Operation {
+                left: Operand::Old,
+                operator: Operator::Multiply,
+                right: Operand::Old,
+            },
+            divisor: 13,
+            if_true: 1,
+            if_false: 3,
+        };
+
+        assert_eq!(monkeys.len(), 4);
+        assert_eq!(monkeys[2], expected);
+        assert_eq!(monkeys[1].operation.to_string(), "new = old + 6");
+    }
+
+    #[test]
+    fn with_relief() {
+        let monkeys = Day11::parse(EXAMPLE).unwrap();
+
+        assert_eq!(inspections(&monkeys, 20, true).unwrap(), [101, 95, 7, 105]);
+        assert_eq!(Day11::part1(&monkeys).unwrap(), "10605");
+    }
+
+    #[test]
+    fn without_relief() {
+        let monkeys = Day11::parse(EXAMPLE).unwrap();
+
+        assert_eq!(inspections(&monkeys, 1, false).unwrap(), [2, 4, 3, 6]);
+        assert_eq!(
+            inspections(&monkeys, 1000, false).unwrap(),
+            [5204, 4792, 199, 5192]
+        );
+        assert_eq!(Day11::part2(&monkeys).unwrap(), "2713310158");
+    }
+
+    #[test]
+    fn overflow_is_an_error() {
+        let spec = EXAMPLE.replace("79, 60, 97", "4294967296");
+        let monkeys = Day11::parse(&spec).unwrap();
+
+        let actual = inspections(&monkeys, 1, true).unwrap_err().to_string();
+
+        assert_eq!(
+            actual,
+            "round 1: monkey 2 overflowed new = old * old with old = 4294967296"
+        );
+    }
+
+    #[test]
+    fn bad_target() {
+        let spec = EXAMPLE.replace("throw to monkey 3", "throw to monkey 7");
+
+        let actual = Day11::parse(&spec).unwrap_err().to_string();
+
+        assert_eq!(actual, "monkey 0 throws to monkey 7, which does not exist");
+    }
+
+    #[test]
+    fn bad_operation() {
+        let spec = EXAMPLE.replace("old + 6", "old - 6");
+
+        let actual = Day11::parse(&spec).unwrap_err().to_string();
+
+        assert!(actual.starts_with("line 10, column 23:"), "{actual}");
+    }
+}
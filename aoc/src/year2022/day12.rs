@@ -0,0 +1,224 @@
+use crate::{
+    solution::Solution,
+    trace,
+    util::{
+        grid::{Grid, Point},
+        search::{self, Path},
+    },
+};
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Hill;
+
+    fn parse(file: &str) -> anyhow::Result<Self::Input> {
+        Hill::parse(file)
+    }
+
+    fn part1(hill: &Self::Input) -> anyhow::Result<String> {
+        let Some(path) = hill.climb() else {
+            anyhow::bail!("E cannot be reached from S");
+        };
+        trace!(
+            "path",
+            steps = path.cost,
+            drawing = hill.render(&path.states)
+        );
+
+        Ok(path.cost.to_string())
+    }
+
+    fn part2(hill: &Self::Input) -> anyhow::Result<String> {
+        let Some(path) = hill.best_start() else {
+            anyhow::bail!("E cannot be reached from any square of elevation a");
+        };
+        trace!(
+            "start",
+            start = path.states[0].to_string(),
+            steps = path.cost,
+        );
+
+        Ok(path.cost.to_string())
+    }
+}
+
+/// The elevation of every square, from 0 for `a` to 25 for `z`.
+#[derive(Debug)]
+pub struct Hill {
+    elevations: Grid<u8>,
+    start: Point,
+    end: Point,
+}
+
+impl Hill {
+    /// Read a map of letters with `S` for the start, at elevation `a`, and
+    /// `E` for the best signal, at elevation `z`.
+    pub fn parse(text: &str) -> anyhow::Result<Self> {
+        let map = Grid::parse(text, |c| match c {
+            'a'..='z' | 'S' | 'E' => Ok(c),
+            _ => anyhow::bail!("expected an elevation, S or E, found {c:?}"),
+        })?;
+        let find = |marker| {
+            let mut found = map.iter().filter(|&(_, &c)| c == marker);
+            match (found.next(), found.next()) {
+                (Some((point, _)), None) => Ok(point),
+                (None, _) => anyhow::bail!("there is no {marker}"),
+                (Some(_), Some((point, _))) => {
+                    anyhow::bail!("there is a second {marker} at {point}")
+                }
+            }
+        };
+        let (start, end) = (find('S')?, find('E')?);
+
+        let elevations = map.map(|&c| match c {
+            'S' => 0,
+            'E' => 25,
+            c => c as u8 - b'a',
+        });
+
+        Ok(Self {
+            elevations,
+            start,
+            end,
+        })
+    }
+
+    /// Squares reachable in one step from `from`, climbing at most one level
+    /// but dropping any number.
+    fn uphill(&self, from: Point) -> impl Iterator<Item = Point> + '_ {
+        let limit = self.elevations[from] + 1;
+        self.elevations
+            .neighbours4(from)
+            .filter(move |&to| self.elevations[to] <= limit)
+    }
+
+    /// Squares that can reach `to` in one step.
+    fn downhill(&self, to: Point) -> impl Iterator<Item = Point> + '_ {
+        let elevation = self.elevations[to];
+        self.elevations
+            .neighbours4(to)
+            .filter(move |&from| self.elevations[from] + 1 >= elevation)
+    }
+
+    /// The shortest path from `S` to `E`.
+    pub fn climb(&self) -> Option<Path<Point, usize>> {
+        search::bfs(
+            self.start,
+            |&point| self.uphill(point),
+            |&point| point == self.end,
+        )
+    }
+
+    /// The shortest path to `E` from any square of elevation `a`, found by
+    /// searching backwards from `E` once rather than forwards from each.
+    pub fn best_start(&self) -> Option<Path<Point, usize>> {
+        let mut path = search::bfs(
+            self.end,
+            |&point| self.downhill(point),
+            |&point| self.elevations[point] == 0,
+        )?;
+        path.states.reverse();
+
+        Some(path)
+    }
+
+    /// The map with the path drawn on it as arrows, like the puzzle does,
+    /// with `E` at the end and `.` everywhere off the path.
+    pub fn render(&self, path: &[Point]) -> String {
+        let mut drawing = self.elevations.map(|_| '.');
+        for step in path.windows(2) {
+            let (from, to) = (step[0], step[1]);
+            drawing[from] = if to.x > from.x {
+                '>'
+            } else if to.x < from.x {
+                '<'
+            } else if to.y > from.y {
+                'v'
+            } else {
+                '^'
+            };
+        }
+        drawing[self.end] = 'E';
+
+        drawing.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Sabqponm
+abcryxxl
+accszExk
+acctuvwj
+abdefghi
+";
+
+    #[test]
+    fn fewest_steps() {
+        let hill = Day12::parse(EXAMPLE).unwrap();
+
+        assert_eq!(Day12::part1(&hill).unwrap(), "31");
+    }
+
+    #[test]
+    fn best_start() {
+        let hill = Day12::parse(EXAMPLE).unwrap();
+
+        let path = hill.best_start().unwrap();
+
+        assert_eq!(path.cost, 29);
+        assert_eq!(hill.elevations[path.states[0]], 0);
+        assert_eq!(path.states.last(), Some(&hill.end));
+        assert_eq!(Day12::part2(&hill).unwrap(), "29");
+    }
+
+    #[test]
+    fn path_only_climbs_one_level_at_a_time() {
+        let hill = Day12::parse(EXAMPLE).unwrap();
+
+        for path in [hill.climb().unwrap(), hill.best_start().unwrap()] {
+            for step in path.states.windows(2) {
+                assert_eq!(
+                    step[0].x.abs_diff(step[1].x) + step[0].y.abs_diff(step[1].y),
+                    1
+                );
+                assert!(hill.elevations[step[1]] <= hill.elevations[step[0]] + 1);
+            }
+        }
+    }
+
+    #[test]
+    fn render_path() {
+        let hill = Day12::parse(EXAMPLE).unwrap();
+        let path = hill.climb().unwrap();
+
+        let actual = hill.render(&path.states);
+
+        assert_eq!(actual.lines().count(), 5);
+        assert_eq!(actual.chars().filter(|c| "<>^v".contains(*c)).count(), 31);
+        assert_eq!(actual.lines().nth(2).unwrap().chars().nth(5), Some('E'));
+        assert!(actual.starts_with('v') || actual.starts_with('>'));
+    }
+
+    #[test]
+    fn unreachable() {
+        let hill = Day12::parse("SazE\n").unwrap();
+
+        assert_eq!(
+            Day12::part1(&hill).unwrap_err().to_string(),
+            "E cannot be reached from S"
+        );
+    }
+
+    #[test]
+    fn markers() {
+        let missing = Day12::parse("abc\nSbc\n").unwrap_err().to_string();
+        let twice = Day12::parse("SbE\nSbc\n").unwrap_err().to_string();
+
+        assert_eq!(missing, "there is no E");
+        assert_eq!(twice, "there is a second S at (0, 1)");
+    }
+}
@@ -0,0 +1,222 @@
+use std::{cmp::Ordering, fmt};
+
+use crate::{
+    solution::Solution,
+    trace,
+    util::parse::{
+        self, alt, blocks, delimited, literal, map, number, pair, separated, terminated, Parser,
+    },
+};
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<(Packet, Packet)>;
+
+    fn parse(file: &str) -> anyhow::Result<Self::Input> {
+        let pairs = pair(terminated(packet, literal("\n")), packet);
+
+        Ok(parse::parse_all(blocks(pairs), file)?)
+    }
+
+    fn part1(pairs: &Self::Input) -> anyhow::Result<String> {
+        let mut total = 0;
+        for (index, (left, right)) in pairs.iter().enumerate() {
+            let in_order = left <= right;
+            trace!("pair", pair = index + 1, in_order = in_order);
+            if in_order {
+                total += index + 1;
+            }
+        }
+
+        Ok(total.to_string())
+    }
+
+    fn part2(pairs: &Self::Input) -> anyhow::Result<String> {
+        Ok(decoder_key(pairs).to_string())
+    }
+}
+
+/// An integer, or a list of packets.
+#[derive(Debug, Clone)]
+pub enum Packet {
+    Integer(u32),
+    List(Vec<Packet>),
+}
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Packet::Integer(left), Packet::Integer(right)) => left.cmp(right),
+            // Lists compare item by item, then the shorter one is smaller.
+            (Packet::List(left), Packet::List(right)) => left.cmp(right),
+            // An integer compared with a list is treated as a list of itself.
+            (Packet::Integer(_), Packet::List(right)) => {
+                std::slice::from_ref(self).cmp(right.as_slice())
+            }
+            (Packet::List(left), Packet::Integer(_)) => {
+                left.as_slice().cmp(std::slice::from_ref(other))
+            }
+        }
+    }
+}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Equal in the puzzle's ordering, so `2` and `[2]` are the same packet.
+impl PartialEq for Packet {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Packet {}
+
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Packet::Integer(value) => write!(f, "{value}"),
+            Packet::List(items) => {
+                write!(f, "[")?;
+                for (index, item) in items.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+/// Where the two divider packets end up when sorted in with every other
+/// packet, counting from 1, multiplied together.
+///
+/// Packets equal to a divider, like `[2]` or `2` for `[[2]]`, could sort on
+/// either side of it, so rather than sorting, each divider goes after only
+/// the packets strictly less than it, and `[[6]]` also after `[[2]]`.
+pub fn decoder_key(pairs: &[(Packet, Packet)]) -> usize {
+    let dividers =
+        [2, 6].map(|value| Packet::List(vec![Packet::List(vec![Packet::Integer(value)])]));
+
+    dividers
+        .iter()
+        .enumerate()
+        .map(|(earlier_dividers, divider)| {
+            let less = pairs
+                .iter()
+                .flat_map(|(left, right)| [left, right])
+                .filter(|&packet| packet < divider)
+                .count();
+            let position = 1 + less + earlier_dividers;
+            trace!("divider", packet = divider.to_string(), position = position);
+            position
+        })
+        .product()
+}
+
+/// A packet such as `[1,[2,[]],3]`.
+fn packet(input: &str) -> parse::Result<'_, Packet> {
+    let list = delimited(literal("["), separated(packet, literal(",")), literal("]"));
+
+    alt((map(number(), Packet::Integer), map(list, Packet::List))).parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "[1,1,3,1,1]
+[1,1,5,1,1]
+
+[[1],[2,3,4]]
+[[1],4]
+
+[9]
+[[8,7,6]]
+
+[[4,4],4,4]
+[[4,4],4,4,4]
+
+[7,7,7,7]
+[7,7,7]
+
+[]
+[3]
+
+[[[]]]
+[[]]
+
+[1,[2,[3,[4,[5,6,7]]]],8,9]
+[1,[2,[3,[4,[5,6,0]]]],8,9]
+";
+
+    fn parse_packet(text: &str) -> Packet {
+        parse::parse_all(packet, text).unwrap()
+    }
+
+    #[test]
+    fn parse_nested() {
+        let actual = parse_packet("[1,[2,[]],3]");
+        let expected = Packet::List(vec![
+            Packet::Integer(1),
+            Packet::List(vec![Packet::Integer(2), Packet::List(vec![])]),
+            Packet::Integer(3),
+        ]);
+
+        assert!(matches!(&actual, Packet::List(items) if items.len() == 3));
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn mixed_comparisons() {
+        let cases = [
+            ("[[1],[2,3,4]]", "[[1],4]", Ordering::Less),
+            ("[9]", "[[8,7,6]]", Ordering::Greater),
+            ("[[4,4],4,4]", "[[4,4],4,4,4]", Ordering::Less),
+            ("[[[]]]", "[[]]", Ordering::Greater),
+            ("[2]", "2", Ordering::Equal),
+            ("[[2]]", "[2]", Ordering::Equal),
+        ];
+
+        for (left, right, expected) in cases {
+            let actual = parse_packet(left).cmp(&parse_packet(right));
+            assert_eq!(actual, expected, "{left} vs {right}");
+        }
+    }
+
+    #[test]
+    fn pairs_in_order() {
+        let pairs = Day13::parse(EXAMPLE).unwrap();
+
+        assert_eq!(pairs.len(), 8);
+        assert_eq!(Day13::part1(&pairs).unwrap(), "13");
+    }
+
+    #[test]
+    fn decoder_key() {
+        let pairs = Day13::parse(EXAMPLE).unwrap();
+
+        assert_eq!(Day13::part2(&pairs).unwrap(), "140");
+    }
+
+    #[test]
+    fn packets_equal_to_dividers() {
+        let pairs = Day13::parse("[[2]]\n[2]\n\n[1]\n[[6]]\n").unwrap();
+
+        // [1] < [[2]] = [2] = [[2]] < [[6]] = [[6]]
+        assert_eq!(super::decoder_key(&pairs), 2 * 5);
+    }
+
+    #[test]
+    fn unclosed_list() {
+        let actual = Day13::parse("[1,[2]\n[3]\n").unwrap_err().to_string();
+
+        assert!(actual.starts_with("line 1, column 7:"), "{actual}");
+    }
+}
@@ -0,0 +1,303 @@
+use std::fmt;
+
+use crate::{
+    solution::Solution,
+    trace,
+    util::{
+        grid::{Grid, Point},
+        parse::{self, lines, literal, number, pair, separated, terminated, Parser},
+    },
+};
+
+/// Where the sand pours in.
+const SOURCE: (usize, usize) = (500, 0);
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Vec<Vec<(usize, usize)>>;
+
+    fn parse(file: &str) -> anyhow::Result<Self::Input> {
+        let paths = parse::parse_all(lines(rock_path()), file)?;
+        for (index, path) in paths.iter().enumerate() {
+            for segment in path.windows(2) {
+                let ((x1, y1), (x2, y2)) = (segment[0], segment[1]);
+                if x1 != x2 && y1 != y2 {
+                    anyhow::bail!("line {}: {x1},{y1} -> {x2},{y2} is not straight", index + 1);
+                }
+            }
+        }
+
+        Ok(paths)
+    }
+
+    fn part1(paths: &Self::Input) -> anyhow::Result<String> {
+        let mut cave = Cave::new(paths, false);
+        let resting = cave.pour();
+        trace!("cave", resting = resting, drawing = cave.to_string());
+
+        Ok(resting.to_string())
+    }
+
+    fn part2(paths: &Self::Input) -> anyhow::Result<String> {
+        let mut cave = Cave::new(paths, true);
+
+        Ok(cave.pour().to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+    Air,
+    Rock,
+    Sand,
+}
+
+/// A slice of the cave wide enough that sand can never reach its sides: it
+/// spreads at most one column per row, so no further than the floor is deep.
+pub struct Cave {
+    cells: Grid<Cell>,
+    /// The x coordinate of the first column, which is negative when the
+    /// floor is deeper than the source is from the left edge.
+    left: isize,
+    /// The lowest rock from the scan.
+    lowest: usize,
+    floor: bool,
+}
+
+impl Cave {
+    /// The cave with its rocks, and with an endless floor two rows below the
+    /// lowest one if `floor` is set.
+    pub fn new(paths: &[Vec<(usize, usize)>], floor: bool) -> Self {
+        let points = || paths.iter().flatten().chain([&SOURCE]);
+        let lowest = points().map(|&(_, y)| y).max().unwrap_or(0);
+        let depth = lowest + 2;
+        let left = points()
+            .map(|&(x, _)| x as isize)
+            .min()
+            .unwrap_or(0)
+            .min(SOURCE.0 as isize - depth as isize);
+        let right = points()
+            .map(|&(x, _)| x)
+            .max()
+            .unwrap_or(0)
+            .max(SOURCE.0 + depth);
+
+        let mut cells = Grid::new((right as isize - left) as usize + 1, depth + 1, Cell::Air);
+        for path in paths {
+            for segment in path.windows(2) {
+                let ((x1, y1), (x2, y2)) = (segment[0], segment[1]);
+                for x in x1.min(x2)..=x1.max(x2) {
+                    for y in y1.min(y2)..=y1.max(y2) {
+                        cells[Point::new((x as isize - left) as usize, y)] = Cell::Rock;
+                    }
+                }
+            }
+        }
+        if floor {
+            for x in 0..cells.width() {
+                cells[Point::new(x, depth)] = Cell::Rock;
+            }
+        }
+
+        Self {
+            cells,
+            left,
+            lowest,
+            floor,
+        }
+    }
+
+    /// Pour sand until it either falls past the lowest rock or blocks the
+    /// source, returning how many units came to rest.
+    ///
+    /// Each unit follows the one before it until that one's resting place, so
+    /// the path so far is kept and the next unit starts from the end of it
+    /// rather than from the source.
+    pub fn pour(&mut self) -> usize {
+        let source = Point::new(self.source(), SOURCE.1);
+        let mut path = vec![source];
+        let mut resting = 0;
+
+        while let Some(&sand) = path.last() {
+            if !self.floor && sand.y > self.lowest {
+                break;
+            }
+
+            let next = [(0, 1), (-1, 1), (1, 1)]
+                .into_iter()
+                .filter_map(|step| sand.offset(step))
+                .find(|&next| self.cells.get(next) == Some(&Cell::Air));
+            match next {
+                Some(next) => path.push(next),
+                None => {
+                    self.cells[sand] = Cell::Sand;
+                    resting += 1;
+                    path.pop();
+                }
+            }
+        }
+
+        resting
+    }
+
+    /// The column the sand pours in at.
+    fn source(&self) -> usize {
+        (SOURCE.0 as isize - self.left) as usize
+    }
+}
+
+/// The cave as the puzzle draws it: rock `#`, sand `o`, air `.` and the
+/// source `+`, cropped to the columns with anything in them.
+impl fmt::Display for Cave {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bottom = if self.floor {
+            self.cells.height() - 1
+        } else {
+            self.lowest
+        };
+        // The floor would fill every column, so look above it.
+        let used = |x| (0..=self.lowest + 1).any(|y| self.cells[Point::new(x, y)] != Cell::Air);
+        let source = self.source();
+        let first = (0..self.cells.width()).find(|&x| used(x)).unwrap_or(source);
+        let last = (0..self.cells.width())
+            .rfind(|&x| used(x))
+            .unwrap_or(source);
+
+        for y in 0..=bottom {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for x in first.min(source)..=last.max(source) {
+                let c = match self.cells[Point::new(x, y)] {
+                    Cell::Rock => '#',
+                    Cell::Sand => 'o',
+                    Cell::Air if (x, y) == (source, SOURCE.1) => '+',
+                    Cell::Air => '.',
+                };
+                write!(f, "{c}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn rock_path<'a>() -> impl Parser<'a, Vec<(usize, usize)>> {
+    let point = pair(terminated(number(), literal(",")), number());
+
+    separated(point, literal(" -> "))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::*;
+
+    const EXAMPLE: &str = "498,4 -> 498,6 -> 496,6
+503,4 -> 502,4 -> 502,9 -> 494,9
+";
+
+    #[test]
+    fn into_the_abyss() {
+        let paths = Day14::parse(EXAMPLE).unwrap();
+
+        assert_eq!(Day14::part1(&paths).unwrap(), "24");
+    }
+
+    #[test]
+    fn onto_the_floor() {
+        let paths = Day14::parse(EXAMPLE).unwrap();
+
+        assert_eq!(Day14::part2(&paths).unwrap(), "93");
+    }
+
+    #[test]
+    fn render_before_and_after() {
+        let paths = Day14::parse(EXAMPLE).unwrap();
+        let mut cave = Cave::new(&paths, false);
+
+        let before = cave.to_string();
+        cave.pour();
+        let after = cave.to_string();
+
+        assert_eq!(
+            before,
+            "\
+......+...
+..........
+..........
+..........
+....#...##
+....#...#.
+..###...#.
+........#.
+........#.
+#########."
+        );
+        assert_eq!(
+            after,
+            "\
+......+...
+..........
+......o...
+.....ooo..
+....#ooo##
+...o#ooo#.
+..###ooo#.
+....oooo#.
+.o.ooooo#.
+#########."
+        );
+    }
+
+    #[test]
+    fn render_with_floor() {
+        let paths = Day14::parse(EXAMPLE).unwrap();
+        let mut cave = Cave::new(&paths, true);
+        cave.pour();
+
+        let drawing = cave.to_string();
+        let rows: Vec<_> = drawing.lines().collect();
+
+        assert_eq!(rows.len(), 12);
+        assert_eq!(rows[0], "..........o..........");
+        assert_eq!(rows[11], "#".repeat(21));
+    }
+
+    /// Rock so deep that the sand spreads further left than `x = 0`.
+    const DEEP: &str = "300,600 -> 301,600\n";
+
+    #[test]
+    fn deeper_than_the_source_is_wide() {
+        let paths = Day14::parse(DEEP).unwrap();
+
+        // A triangle 602 rows deep, less the two rocks.
+        assert_eq!(Day14::part2(&paths).unwrap(), (602 * 602 - 2).to_string());
+    }
+
+    /// Filling over 360,000 units takes a few milliseconds with the path
+    /// kept between units, and far longer without, which only shows in an
+    /// optimised build.
+    #[test]
+    #[cfg_attr(debug_assertions, ignore)]
+    fn floor_in_milliseconds() {
+        let paths = Day14::parse(DEEP).unwrap();
+
+        let start = Instant::now();
+        Day14::part2(&paths).unwrap();
+        let elapsed = start.elapsed();
+
+        assert!(elapsed < Duration::from_millis(100), "took {elapsed:?}");
+    }
+
+    #[test]
+    fn diagonal_path() {
+        let actual = Day14::parse("498,4 -> 498,6\n1,1 -> 2,2\n")
+            .unwrap_err()
+            .to_string();
+
+        assert_eq!(actual, "line 2: 1,1 -> 2,2 is not straight");
+    }
+}
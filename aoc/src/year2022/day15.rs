@@ -0,0 +1,216 @@
+use crate::{
+    solution::Solution,
+    trace,
+    util::{
+        interval::IntervalSet,
+        parse::{self, lines, literal, number, pair, preceded, Parser},
+    },
+};
+
+/// The row to check for places a beacon cannot be.
+const ROW: i64 = 2_000_000;
+/// The distress beacon is somewhere in `0..=SEARCH_SIZE` on both axes.
+const SEARCH_SIZE: i64 = 4_000_000;
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = Vec<Sensor>;
+
+    fn parse(file: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse::parse_all(lines(sensor()), file)?)
+    }
+
+    fn part1(sensors: &Self::Input) -> anyhow::Result<String> {
+        Ok(no_beacon(sensors, ROW).to_string())
+    }
+
+    fn part2(sensors: &Self::Input) -> anyhow::Result<String> {
+        let Some((x, y)) = distress_beacon(sensors, SEARCH_SIZE) else {
+            anyhow::bail!("every position in 0..={SEARCH_SIZE} is covered");
+        };
+
+        Ok((x * 4_000_000 + y).to_string())
+    }
+}
+
+/// A sensor and the closest beacon to it, which leaves no other beacon as
+/// close in the Manhattan distance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sensor {
+    position: (i64, i64),
+    beacon: (i64, i64),
+}
+
+impl Sensor {
+    fn radius(&self) -> i64 {
+        distance(self.position, self.beacon)
+    }
+
+    fn covers(&self, point: (i64, i64)) -> bool {
+        distance(self.position, point) <= self.radius()
+    }
+
+    /// Where the sensor's diamond crosses row `y`, if it does.
+    fn coverage(&self, y: i64) -> Option<std::ops::RangeInclusive<i64>> {
+        let (x, sensor_y) = self.position;
+        let reach = self.radius() - (y - sensor_y).abs();
+
+        (reach >= 0).then(|| x - reach..=x + reach)
+    }
+}
+
+fn distance((x1, y1): (i64, i64), (x2, y2): (i64, i64)) -> i64 {
+    (x1 - x2).abs() + (y1 - y2).abs()
+}
+
+/// Columns of row `y` covered by any sensor.
+pub fn covered(sensors: &[Sensor], y: i64) -> IntervalSet {
+    sensors
+        .iter()
+        .filter_map(|sensor| sensor.coverage(y))
+        .collect()
+}
+
+/// How many places on row `y` are covered by a sensor without already
+/// holding a known beacon.
+pub fn no_beacon(sensors: &[Sensor], y: i64) -> u128 {
+    let covered = covered(sensors, y);
+    let beacons: IntervalSet = sensors
+        .iter()
+        .filter(|sensor| sensor.beacon.1 == y)
+        .map(|sensor| sensor.beacon.0..=sensor.beacon.0)
+        .collect();
+    trace!(
+        "row",
+        y = y,
+        covered = covered.to_string(),
+        beacons = beacons.to_string()
+    );
+
+    covered.difference(&beacons).len()
+}
+
+/// The only place in the square from 0 to `size` that no sensor covers.
+///
+/// A single uncovered place is boxed in by diamonds on every side, so it lies
+/// just outside some diamond's edge on each diagonal, or on the square's
+/// edge. Only the crossings of those lines need checking.
+pub fn distress_beacon(sensors: &[Sensor], size: i64) -> Option<(i64, i64)> {
+    // Lines `x - y = a` and `x + y = b` one step outside each diamond.
+    let mut rising = Vec::new();
+    let mut falling = Vec::new();
+    for sensor in sensors {
+        let (x, y) = sensor.position;
+        let outside = sensor.radius() + 1;
+        rising.extend([x - y - outside, x - y + outside]);
+        falling.extend([x + y - outside, x + y + outside]);
+    }
+
+    let crossings = rising.iter().flat_map(|&a| {
+        falling
+            .iter()
+            .filter(move |&&b| (a + b) % 2 == 0)
+            .map(move |&b| ((a + b) / 2, (b - a) / 2))
+    });
+    let edges = [0, size].into_iter().flat_map(|edge| {
+        let rising = rising
+            .iter()
+            .flat_map(move |&a| [(edge, edge - a), (a + edge, edge)]);
+        let falling = falling
+            .iter()
+            .flat_map(move |&b| [(edge, b - edge), (b - edge, edge)]);
+        rising.chain(falling)
+    });
+    let corners = [(0, 0), (0, size), (size, 0), (size, size)];
+
+    crossings
+        .chain(edges)
+        .chain(corners)
+        .filter(|&(x, y)| (0..=size).contains(&x) && (0..=size).contains(&y))
+        .find(|&point| !sensors.iter().any(|sensor| sensor.covers(point)))
+}
+
+fn sensor<'a>() -> impl Parser<'a, Sensor> {
+    let point = || pair(number(), preceded(literal(", y="), number()));
+    let position = preceded(literal("Sensor at x="), point());
+    let beacon = preceded(literal(": closest beacon is at x="), point());
+
+    parse::map(pair(position, beacon), |(position, beacon)| Sensor {
+        position,
+        beacon,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3
+";
+
+    #[test]
+    fn row_coverage() {
+        let sensors = Day15::parse(EXAMPLE).unwrap();
+
+        assert_eq!(covered(&sensors, 10).to_string(), "-2..=24");
+        assert_eq!(no_beacon(&sensors, 10), 26);
+    }
+
+    #[test]
+    fn distress_beacon_in_example() {
+        let sensors = Day15::parse(EXAMPLE).unwrap();
+
+        assert_eq!(distress_beacon(&sensors, 20), Some((14, 11)));
+    }
+
+    #[test]
+    fn matches_checking_every_row() {
+        let sensors = Day15::parse(EXAMPLE).unwrap();
+
+        let by_rows: Vec<_> = (0..=20)
+            .flat_map(|y| {
+                let gaps = covered(&sensors, y).gaps(0..=20);
+                gaps.iter()
+                    .flatten()
+                    .map(move |x| (x, y))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        assert_eq!(by_rows, vec![(14, 11)]);
+    }
+
+    #[test]
+    fn uncovered_corner() {
+        // One sensor covering all but the far corner of a 4x4 square.
+        let sensors = [Sensor {
+            position: (0, 0),
+            beacon: (5, 0),
+        }];
+
+        assert_eq!(distress_beacon(&sensors, 3), Some((3, 3)));
+    }
+
+    #[test]
+    fn bad_line() {
+        let actual = Day15::parse("Sensor at x=2, y=18: closest beacon at x=-2, y=15\n")
+            .unwrap_err()
+            .to_string();
+
+        assert!(actual.starts_with("line 1, column 20:"), "{actual}");
+    }
+}
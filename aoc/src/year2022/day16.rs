@@ -0,0 +1,369 @@
+use std::collections::{hash_map::Entry, HashMap};
+
+use crate::{
+    solution::Solution,
+    trace,
+    util::{
+        parse::{
+            self, alt, lines, literal, number, pair, preceded, separated, take_while, try_map,
+            Parser,
+        },
+        search,
+    },
+};
+
+/// Where we start, with every valve closed.
+const START: &str = "AA";
+/// The most valves with a flow that the search keeps a table entry for every
+/// subset of.
+const MAX_FLOWING: usize = 20;
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = Network;
+
+    fn parse(file: &str) -> anyhow::Result<Self::Input> {
+        let valves = parse::parse_all(lines(valve()), file)?;
+
+        Network::new(&valves)
+    }
+
+    fn part1(network: &Self::Input) -> anyhow::Result<String> {
+        let best = network.best_by_opened(30).best;
+        let (opened, pressure) = (0..best.len())
+            .map(|opened| (opened, best[opened]))
+            .max_by_key(|&(_, pressure)| pressure)
+            .unwrap_or_default();
+        trace!("plan", pressure = pressure, opened = network.names(opened));
+
+        Ok(pressure.to_string())
+    }
+
+    fn part2(network: &Self::Input) -> anyhow::Result<String> {
+        Ok(network.with_elephant(26).to_string())
+    }
+}
+
+/// One line of the scan: a valve, its flow rate and where its tunnels go.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Valve<'a> {
+    name: &'a str,
+    rate: u32,
+    tunnels: Vec<&'a str>,
+}
+
+/// The valves worth opening, with the time it takes to walk between them.
+///
+/// Valves without a flow only matter as places to walk through, so they are
+/// left out once the distances are known. Valve `i` is bit `i` of a set of
+/// opened valves.
+#[derive(Debug)]
+pub struct Network {
+    names: Vec<String>,
+    rates: Vec<u32>,
+    /// Minutes to walk from one valve to another, with the start as the row
+    /// after the last valve. `None` where there is no way there.
+    distances: Vec<Vec<Option<u32>>>,
+}
+
+impl Network {
+    pub fn new(valves: &[Valve]) -> anyhow::Result<Self> {
+        let index: HashMap<&str, usize> = valves
+            .iter()
+            .enumerate()
+            .map(|(index, valve)| (valve.name, index))
+            .collect();
+        for valve in valves {
+            for tunnel in &valve.tunnels {
+                if !index.contains_key(tunnel) {
+                    anyhow::bail!(
+                        "valve {} has a tunnel to {tunnel}, which does not exist",
+                        valve.name
+                    );
+                }
+            }
+        }
+        let Some(&start) = index.get(START) else {
+            anyhow::bail!("there is no valve {START} to start from");
+        };
+
+        let flowing: Vec<usize> = (0..valves.len()).filter(|&v| valves[v].rate > 0).collect();
+        if flowing.len() > MAX_FLOWING {
+            anyhow::bail!(
+                "{} valves have a flow, but at most {MAX_FLOWING} can be searched",
+                flowing.len()
+            );
+        }
+
+        let distances = flowing
+            .iter()
+            .chain([&start])
+            .map(|&from| {
+                let reached = search::distances(from, |&valve| {
+                    valves[valve].tunnels.iter().map(|tunnel| index[tunnel])
+                });
+                flowing
+                    .iter()
+                    .map(|to| reached.get(to).map(|&steps| steps as u32))
+                    .collect()
+            })
+            .collect();
+
+        Ok(Self {
+            names: flowing
+                .iter()
+                .map(|&v| valves[v].name.to_string())
+                .collect(),
+            rates: flowing.iter().map(|&v| valves[v].rate).collect(),
+            distances,
+        })
+    }
+
+    /// The names of the valves in `opened`, in scan order.
+    fn names(&self, opened: usize) -> Vec<String> {
+        (0..self.names.len())
+            .filter(|valve| opened & (1 << valve) != 0)
+            .map(|valve| self.names[valve].clone())
+            .collect()
+    }
+
+    /// The most pressure one actor can release in `minutes` for every set of
+    /// valves it could open.
+    pub fn best_by_opened(&self, minutes: u32) -> Search {
+        let mut search = Search {
+            best: vec![0; 1 << self.rates.len()],
+            seen: HashMap::new(),
+            states: 0,
+        };
+        self.explore(self.rates.len(), minutes, 0, 0, &mut search);
+        trace!("search", minutes = minutes, states = search.states);
+
+        search
+    }
+
+    /// Record the best pressure for every set of valves that can be opened
+    /// from here.
+    ///
+    /// Reaching a valve with the same time left and the same valves open as
+    /// before leaves the same choices, so it is only worth going on if more
+    /// pressure has been released this time.
+    fn explore(&self, at: usize, minutes: u32, opened: usize, pressure: u32, search: &mut Search) {
+        match search.seen.entry((at, minutes, opened)) {
+            Entry::Occupied(entry) if *entry.get() >= pressure => return,
+            Entry::Occupied(mut entry) => {
+                entry.insert(pressure);
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(pressure);
+            }
+        }
+        search.states += 1;
+        search.best[opened] = search.best[opened].max(pressure);
+
+        for (next, &distance) in self.distances[at].iter().enumerate() {
+            if opened & (1 << next) != 0 {
+                continue;
+            }
+            // Walking there and a minute to open it.
+            let Some(distance) = distance else { continue };
+            let Some(left) = minutes.checked_sub(distance + 1).filter(|&left| left > 0) else {
+                continue;
+            };
+
+            let released = pressure + self.rates[next] * left;
+            self.explore(next, left, opened | (1 << next), released, search);
+        }
+    }
+
+    /// The most pressure two actors can release in `minutes`, each opening
+    /// valves the other does not.
+    ///
+    /// Once each set has its best, every set is given the best of any subset
+    /// of it, so pairing a set with the best of whatever the other actor is
+    /// left is a single lookup.
+    pub fn with_elephant(&self, minutes: u32) -> u32 {
+        let best = self.best_by_opened(minutes).best;
+        let mut within = best.clone();
+        for valve in 0..self.rates.len() {
+            for opened in 0..within.len() {
+                if opened & (1 << valve) != 0 {
+                    within[opened] = within[opened].max(within[opened ^ (1 << valve)]);
+                }
+            }
+        }
+
+        let all = best.len() - 1;
+        let (mine, pressure) = (0..best.len())
+            .map(|opened| (opened, best[opened] + within[all ^ opened]))
+            .max_by_key(|&(_, pressure)| pressure)
+            .unwrap_or_default();
+        trace!(
+            "plan",
+            pressure = pressure,
+            mine = self.names(mine),
+            elephant = self.names(all ^ mine)
+        );
+
+        pressure
+    }
+}
+
+/// What [`Network::best_by_opened`] found, and how much searching it took.
+pub struct Search {
+    /// The most pressure released for each set of opened valves, indexed by
+    /// the set. Any set that cannot be opened in time is left at 0, which is
+    /// still reachable by opening nothing at all.
+    pub best: Vec<u32>,
+    /// The most pressure released on reaching each valve with some time
+    /// left and some valves open.
+    seen: HashMap<(usize, u32, usize), u32>,
+    /// How many states were explored.
+    pub states: usize,
+}
+
+fn valve<'a>() -> impl Parser<'a, Valve<'a>> {
+    let name = || {
+        try_map(take_while(|c| c.is_ascii_uppercase()), |name: &str| {
+            if name.is_empty() {
+                Err("a valve name")
+            } else {
+                Ok(name)
+            }
+        })
+    };
+    let tunnels = preceded(
+        alt((
+            literal("; tunnels lead to valves "),
+            literal("; tunnel leads to valve "),
+        )),
+        separated(name(), literal(", ")),
+    );
+
+    parse::map(
+        pair(
+            preceded(literal("Valve "), name()),
+            pair(preceded(literal(" has flow rate="), number()), tunnels),
+        ),
+        |(name, (rate, tunnels))| Valve {
+            name,
+            rate,
+            tunnels,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::*;
+
+    const EXAMPLE: &str = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+Valve BB has flow rate=13; tunnels lead to valves CC, AA
+Valve CC has flow rate=2; tunnels lead to valves DD, BB
+Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+Valve EE has flow rate=3; tunnels lead to valves FF, DD
+Valve FF has flow rate=0; tunnels lead to valves EE, GG
+Valve GG has flow rate=0; tunnels lead to valves FF, HH
+Valve HH has flow rate=22; tunnel leads to valve GG
+Valve II has flow rate=0; tunnels lead to valves AA, JJ
+Valve JJ has flow rate=21; tunnel leads to valve II
+";
+
+    /// A scan as large as a real one: sixty valves in a ring with
+    /// shortcuts across it, and fifteen of them flowing.
+    fn large_scan() -> String {
+        let size = 60;
+        let name = |n: usize| {
+            let letter = |n: usize| (b'A' + n as u8) as char;
+            format!("{}{}", letter(n / 26), letter(n % 26))
+        };
+        (0..size)
+            .map(|n| {
+                let rate = if n % 4 == 1 { 3 + n * 7 % 20 } else { 0 };
+                let tunnels: Vec<_> = [1, size - 1, 13, size - 13]
+                    .iter()
+                    .map(|step| name((n + step) % size))
+                    .collect();
+                format!(
+                    "Valve {} has flow rate={rate}; tunnels lead to valves {}\n",
+                    name(n),
+                    tunnels.join(", ")
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn compressed() {
+        let network = Day16::parse(EXAMPLE).unwrap();
+
+        assert_eq!(network.names, ["BB", "CC", "DD", "EE", "HH", "JJ"]);
+        // From the start, then from HH back to JJ through the start.
+        assert_eq!(network.distances[6][4], Some(5));
+        assert_eq!(network.distances[4][5], Some(7));
+    }
+
+    #[test]
+    fn alone() {
+        let network = Day16::parse(EXAMPLE).unwrap();
+
+        assert_eq!(Day16::part1(&network).unwrap(), "1651");
+    }
+
+    #[test]
+    fn with_an_elephant() {
+        let network = Day16::parse(EXAMPLE).unwrap();
+
+        assert_eq!(Day16::part2(&network).unwrap(), "1707");
+    }
+
+    #[test]
+    fn within_budget() {
+        let network = Day16::parse(&large_scan()).unwrap();
+        assert_eq!(network.names.len(), 15);
+
+        let states = network.best_by_opened(30).states + network.best_by_opened(26).states;
+
+        // Counting states rather than time keeps this the same on any
+        // machine; both parts together visit about 1.3 million.
+        assert!(states < 1_500_000, "visited {states} states");
+    }
+
+    /// The same budget in time, which only means something in an optimised
+    /// build.
+    #[test]
+    #[cfg_attr(debug_assertions, ignore)]
+    fn within_time_budget() {
+        let network = Day16::parse(&large_scan()).unwrap();
+
+        let start = Instant::now();
+        Day16::part1(&network).unwrap();
+        Day16::part2(&network).unwrap();
+        let elapsed = start.elapsed();
+
+        assert!(elapsed < Duration::from_secs(1), "took {elapsed:?}");
+    }
+
+    #[test]
+    fn unknown_tunnel() {
+        let scan = EXAMPLE.replace("valve GG\n", "valve ZZ\n");
+
+        let actual = Day16::parse(&scan).unwrap_err().to_string();
+
+        assert_eq!(actual, "valve HH has a tunnel to ZZ, which does not exist");
+    }
+
+    #[test]
+    fn bad_line() {
+        let actual = Day16::parse("Valve AA has flow rate=0; tunnels lead to valves BB, \n")
+            .unwrap_err()
+            .to_string();
+
+        assert_eq!(
+            actual,
+            "line 1, column 54: expected a valve name, found end of line"
+        );
+    }
+}
@@ -0,0 +1,270 @@
+use std::fmt;
+
+use crate::{solution::Solution, trace, util::cycle};
+
+/// The chamber is seven units wide; each row is a bitmask with column `x` as
+/// bit `x`.
+const WIDTH: usize = 7;
+const FULL_ROW: u8 = (1 << WIDTH) - 1;
+
+/// The rocks in the order they fall, as rows from the bottom up, already two
+/// units from the left wall as they appear.
+const ROCKS: [&[u8]; 5] = [
+    // ####
+    &[0b0111100],
+    // .#.
+    // ###
+    // .#.
+    &[0b0001000, 0b0011100, 0b0001000],
+    // ..#
+    // ..#
+    // ###
+    &[0b0011100, 0b0010000, 0b0010000],
+    // #
+    // #
+    // #
+    // #
+    &[0b0000100, 0b0000100, 0b0000100, 0b0000100],
+    // ##
+    // ##
+    &[0b0001100, 0b0001100],
+];
+
+/// How many rows from the top of the tower go into a state, which is far
+/// deeper than any rock gets through the gaps in a real one.
+const SURFACE_DEPTH: usize = 48;
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = Vec<Jet>;
+
+    fn parse(file: &str) -> anyhow::Result<Self::Input> {
+        let jets = file
+            .trim_end()
+            .chars()
+            .enumerate()
+            .map(|(index, c)| match c {
+                '<' => Ok(Jet::Left),
+                '>' => Ok(Jet::Right),
+                _ => anyhow::bail!("column {}: expected < or >, found {c:?}", index + 1),
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        if jets.is_empty() {
+            anyhow::bail!("there are no jets");
+        }
+
+        Ok(jets)
+    }
+
+    fn part1(jets: &Self::Input) -> anyhow::Result<String> {
+        Ok(tower_height(jets, 2022).to_string())
+    }
+
+    fn part2(jets: &Self::Input) -> anyhow::Result<String> {
+        Ok(tower_height(jets, 1_000_000_000_000).to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Jet {
+    Left,
+    Right,
+}
+
+/// The tower so far, and where both the rocks and the jets are up to.
+pub struct Chamber<'a> {
+    /// From the bottom up, with no empty rows at the top.
+    rows: Vec<u8>,
+    jets: &'a [Jet],
+    jet: usize,
+    rocks: usize,
+}
+
+impl<'a> Chamber<'a> {
+    pub fn new(jets: &'a [Jet]) -> Self {
+        Self {
+            rows: Vec::new(),
+            jets,
+            jet: 0,
+            rocks: 0,
+        }
+    }
+
+    pub fn height(&self) -> u64 {
+        self.rows.len() as u64
+    }
+
+    fn collides(&self, rock: &[u8], y: usize) -> bool {
+        rock.iter()
+            .zip(self.rows.iter().skip(y))
+            .any(|(rock, row)| rock & row != 0)
+    }
+
+    /// Let the next rock fall until it comes to rest.
+    pub fn drop_rock(&mut self) {
+        let mut rock = ROCKS[self.rocks % ROCKS.len()].to_vec();
+        self.rocks += 1;
+        let mut y = self.rows.len() + 3;
+
+        loop {
+            let jet = self.jets[self.jet];
+            self.jet = (self.jet + 1) % self.jets.len();
+            let pushed: Option<Vec<u8>> = match jet {
+                Jet::Left if rock.iter().all(|row| row & 1 == 0) => {
+                    Some(rock.iter().map(|row| row >> 1).collect())
+                }
+                Jet::Right if rock.iter().all(|row| row & (1 << (WIDTH - 1)) == 0) => {
+                    Some(rock.iter().map(|row| row << 1).collect())
+                }
+                _ => None,
+            };
+            if let Some(pushed) = pushed.filter(|pushed| !self.collides(pushed, y)) {
+                rock = pushed;
+            }
+
+            if y == 0 || self.collides(&rock, y - 1) {
+                break;
+            }
+            y -= 1;
+        }
+
+        for (offset, row) in rock.into_iter().enumerate() {
+            match self.rows.get_mut(y + offset) {
+                Some(existing) => *existing |= row,
+                None => self.rows.push(row),
+            }
+        }
+    }
+
+    /// Everything that decides how the tower grows from here: the next rock,
+    /// the next jet and the shape of the top of the tower, with the floor
+    /// counting as a full row.
+    fn state(&self) -> (usize, usize, [u8; SURFACE_DEPTH]) {
+        let mut surface = [FULL_ROW; SURFACE_DEPTH];
+        for (row, top) in surface.iter_mut().zip(self.rows.iter().rev()) {
+            *row = *top;
+        }
+
+        (self.rocks % ROCKS.len(), self.jet, surface)
+    }
+}
+
+/// The tower as the puzzle draws it, with `#` for rock and the floor at the
+/// bottom.
+impl fmt::Display for Chamber<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows.iter().rev() {
+            write!(f, "|")?;
+            for x in 0..WIDTH {
+                let c = if row & (1 << x) != 0 { '#' } else { '.' };
+                write!(f, "{c}")?;
+            }
+            writeln!(f, "|")?;
+        }
+        write!(f, "+{}+", "-".repeat(WIDTH))
+    }
+}
+
+/// How tall the tower is after `rocks` rocks have fallen.
+///
+/// Rocks are dropped only until the state of the chamber repeats. From then
+/// on it grows by the same amount each time around, so the height of any
+/// later tower follows from the heights seen so far.
+pub fn tower_height(jets: &[Jet], rocks: usize) -> u64 {
+    let mut chamber = Chamber::new(jets);
+    let mut heights = vec![0];
+    let first = chamber.state();
+    let states = std::iter::once(first).chain(std::iter::from_fn(|| {
+        if heights.len() > rocks {
+            return None;
+        }
+        chamber.drop_rock();
+        heights.push(chamber.height());
+        Some(chamber.state())
+    }));
+
+    match cycle::find(states) {
+        Some(found) => {
+            trace!(
+                "cycle",
+                start = found.start,
+                length = found.length,
+                height = heights[found.start + found.length] - heights[found.start]
+            );
+            found.extrapolate(&heights, rocks)
+        }
+        None => heights[rocks],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>\n";
+
+    fn simulate(jets: &[Jet], rocks: usize) -> Vec<u64> {
+        let mut chamber = Chamber::new(jets);
+        let mut heights = vec![0];
+        for _ in 0..rocks {
+            chamber.drop_rock();
+            heights.push(chamber.height());
+        }
+
+        heights
+    }
+
+    #[test]
+    fn first_rocks() {
+        let jets = Day17::parse(EXAMPLE).unwrap();
+        let mut chamber = Chamber::new(&jets);
+        for _ in 0..3 {
+            chamber.drop_rock();
+        }
+
+        assert_eq!(
+            chamber.to_string(),
+            "\
+|..#....|
+|..#....|
+|####...|
+|..###..|
+|...#...|
+|..####.|
++-------+"
+        );
+    }
+
+    #[test]
+    fn height_after_2022() {
+        let jets = Day17::parse(EXAMPLE).unwrap();
+
+        assert_eq!(simulate(&jets, 2022)[2022], 3068);
+        assert_eq!(Day17::part1(&jets).unwrap(), "3068");
+    }
+
+    #[test]
+    fn height_after_a_trillion() {
+        let jets = Day17::parse(EXAMPLE).unwrap();
+
+        assert_eq!(Day17::part2(&jets).unwrap(), "1514285714288");
+    }
+
+    #[test]
+    fn matches_simulation() {
+        let jets = Day17::parse(EXAMPLE).unwrap();
+        let heights = simulate(&jets, 5000);
+
+        for rocks in [0, 1, 17, 500, 2500, 4999, 5000] {
+            assert_eq!(tower_height(&jets, rocks), heights[rocks], "{rocks} rocks");
+        }
+    }
+
+    #[test]
+    fn bad_jet() {
+        let actual = Day17::parse(">><v<\n").unwrap_err().to_string();
+
+        assert_eq!(actual, "column 4: expected < or >, found 'v'");
+    }
+}
@@ -0,0 +1,140 @@
+use crate::{
+    solution::Solution,
+    trace,
+    util::{
+        parse::{self, lines, literal, number, pair, terminated, Parser},
+        search,
+        voxel::{Point3, VoxelSet},
+    },
+};
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = VoxelSet;
+
+    fn parse(file: &str) -> anyhow::Result<Self::Input> {
+        let cubes = parse::parse_all(lines(cube()), file)?;
+
+        Ok(cubes.into_iter().collect())
+    }
+
+    fn part1(droplet: &Self::Input) -> anyhow::Result<String> {
+        Ok(surface_area(droplet).to_string())
+    }
+
+    fn part2(droplet: &Self::Input) -> anyhow::Result<String> {
+        Ok(exterior_surface_area(droplet).to_string())
+    }
+}
+
+/// Faces of the cubes not shared with another cube, including those facing
+/// air pockets inside the droplet.
+pub fn surface_area(droplet: &VoxelSet) -> usize {
+    droplet
+        .iter()
+        .map(|cube| 6 - droplet.neighbours6(cube).count())
+        .sum()
+}
+
+/// Faces of the cubes that steam from outside the droplet can reach.
+///
+/// The steam spreads from a corner of a box one larger than the droplet on
+/// every side, so it can flow all the way around.
+pub fn exterior_surface_area(droplet: &VoxelSet) -> usize {
+    let Some((min, max)) = droplet.bounds() else {
+        return 0;
+    };
+    let (min, max) = (min.offset((-1, -1, -1)), max.offset((1, 1, 1)));
+    let inside = |p: Point3| {
+        (min.x..=max.x).contains(&p.x)
+            && (min.y..=max.y).contains(&p.y)
+            && (min.z..=max.z).contains(&p.z)
+    };
+
+    let steam = search::distances(min, |&air: &Point3| {
+        air.neighbours6()
+            .filter(move |&next| inside(next) && !droplet.contains(next))
+    });
+    trace!("steam", cells = steam.len());
+
+    droplet
+        .iter()
+        .flat_map(Point3::neighbours6)
+        .filter(|face| steam.contains_key(face))
+        .count()
+}
+
+fn cube<'a>() -> impl Parser<'a, Point3> {
+    let coordinate = || terminated(number(), literal(","));
+
+    parse::map(
+        pair(coordinate(), pair(coordinate(), number())),
+        |(x, (y, z))| Point3::new(x, y, z),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "2,2,2
+1,2,2
+3,2,2
+2,1,2
+2,3,2
+2,2,1
+2,2,3
+2,2,4
+2,2,6
+1,2,5
+3,2,5
+2,1,5
+2,3,5
+";
+
+    #[test]
+    fn two_cubes() {
+        let droplet = Day18::parse("1,1,1\n2,1,1\n").unwrap();
+
+        assert_eq!(surface_area(&droplet), 10);
+        assert_eq!(exterior_surface_area(&droplet), 10);
+    }
+
+    #[test]
+    fn example() {
+        let droplet = Day18::parse(EXAMPLE).unwrap();
+
+        assert_eq!(Day18::part1(&droplet).unwrap(), "64");
+        assert_eq!(Day18::part2(&droplet).unwrap(), "58");
+    }
+
+    #[test]
+    fn hollow_cube() {
+        // A 3x3x3 cube with its centre missing.
+        let droplet: VoxelSet = (0..27)
+            .map(|n| Point3::new(n % 3, n / 3 % 3, n / 9))
+            .filter(|&p| p != Point3::new(1, 1, 1))
+            .collect();
+
+        assert_eq!(surface_area(&droplet), 54 + 6);
+        assert_eq!(exterior_surface_area(&droplet), 54);
+    }
+
+    #[test]
+    fn empty() {
+        let droplet = Day18::parse("").unwrap();
+
+        assert_eq!(Day18::part2(&droplet).unwrap(), "0");
+    }
+
+    #[test]
+    fn bad_line() {
+        let actual = Day18::parse("1,2,3\n4,5\n").unwrap_err().to_string();
+
+        assert_eq!(
+            actual,
+            "line 2, column 4: expected \",\", found end of line"
+        );
+    }
+}
@@ -0,0 +1,273 @@
+use rayon::prelude::*;
+
+use crate::{
+    solution::Solution,
+    trace,
+    util::parse::{self, delimited, lines, literal, number, pair, preceded, Parser},
+};
+
+const ORE: usize = 0;
+const CLAY: usize = 1;
+const OBSIDIAN: usize = 2;
+const GEODE: usize = 3;
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = Vec<Blueprint>;
+
+    fn parse(file: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse::parse_all(lines(blueprint()), file)?)
+    }
+
+    fn part1(blueprints: &Self::Input) -> anyhow::Result<String> {
+        let total: u32 = max_geodes_each(blueprints, 24)
+            .iter()
+            .zip(blueprints)
+            .map(|(geodes, blueprint)| blueprint.id * geodes)
+            .sum();
+
+        Ok(total.to_string())
+    }
+
+    fn part2(blueprints: &Self::Input) -> anyhow::Result<String> {
+        let first = &blueprints[..blueprints.len().min(3)];
+        let product: u64 = max_geodes_each(first, 32)
+            .iter()
+            .map(|&geodes| u64::from(geodes))
+            .product();
+
+        Ok(product.to_string())
+    }
+}
+
+/// What each kind of robot costs, in ore, clay and obsidian.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Blueprint {
+    id: u32,
+    costs: [[u32; 3]; 4],
+}
+
+impl Blueprint {
+    /// The most robots of each kind worth having. A factory builds one robot
+    /// a minute, so collecting more of a resource than the dearest robot
+    /// needs in a minute is wasted. Geode robots are always worth it.
+    fn max_robots(&self) -> [u32; 4] {
+        let mut max = [u32::MAX; 4];
+        for resource in [ORE, CLAY, OBSIDIAN] {
+            max[resource] = self
+                .costs
+                .iter()
+                .map(|cost| cost[resource])
+                .max()
+                .unwrap_or(0);
+        }
+
+        max
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct State {
+    minutes: u32,
+    robots: [u32; 4],
+    resources: [u32; 4],
+}
+
+/// The most geodes `blueprint` can open in `minutes`, starting with one ore
+/// robot.
+///
+/// Rather than choosing what to do each minute, each step of the search
+/// picks which robot to build next and skips ahead to when it is done.
+pub fn max_geodes(blueprint: &Blueprint, minutes: u32) -> u32 {
+    let start = State {
+        minutes,
+        robots: [1, 0, 0, 0],
+        resources: [0; 4],
+    };
+    let mut best = 0;
+    search(blueprint, &blueprint.max_robots(), start, &mut best);
+
+    best
+}
+
+fn search(blueprint: &Blueprint, max_robots: &[u32; 4], state: State, best: &mut u32) {
+    let State {
+        minutes,
+        robots,
+        resources,
+    } = state;
+    // Building nothing more still opens this many.
+    let geodes = resources[GEODE] + robots[GEODE] * minutes;
+    *best = (*best).max(geodes);
+
+    // Even a new geode robot every minute from now on would not do better.
+    let optimistic = geodes + minutes * minutes.saturating_sub(1) / 2;
+    if optimistic <= *best {
+        return;
+    }
+
+    for robot in [GEODE, OBSIDIAN, CLAY, ORE] {
+        if robots[robot] >= max_robots[robot] {
+            continue;
+        }
+        let Some(waiting) = time_to_afford(&blueprint.costs[robot], &robots, &resources) else {
+            continue;
+        };
+        // A robot finished in the last minute has no time left to collect.
+        let elapsed = waiting + 1;
+        if elapsed >= minutes {
+            continue;
+        }
+
+        let mut next = State {
+            minutes: minutes - elapsed,
+            robots,
+            resources,
+        };
+        for resource in [ORE, CLAY, OBSIDIAN, GEODE] {
+            next.resources[resource] += robots[resource] * elapsed;
+        }
+        for (resource, cost) in blueprint.costs[robot].iter().enumerate() {
+            next.resources[resource] -= cost;
+        }
+        next.robots[robot] += 1;
+
+        search(blueprint, max_robots, next, best);
+    }
+}
+
+/// Minutes of collecting before `cost` can be paid, or `None` if a resource
+/// it needs is not being collected at all.
+fn time_to_afford(cost: &[u32; 3], robots: &[u32; 4], resources: &[u32; 4]) -> Option<u32> {
+    let mut waiting = 0;
+    for resource in [ORE, CLAY, OBSIDIAN] {
+        let missing = cost[resource].saturating_sub(resources[resource]);
+        if missing > 0 {
+            if robots[resource] == 0 {
+                return None;
+            }
+            waiting = waiting.max(missing.div_ceil(robots[resource]));
+        }
+    }
+
+    Some(waiting)
+}
+
+/// The most geodes each blueprint can open, searched in parallel.
+pub fn max_geodes_each(blueprints: &[Blueprint], minutes: u32) -> Vec<u32> {
+    let geodes: Vec<u32> = blueprints
+        .par_iter()
+        .map(|blueprint| max_geodes(blueprint, minutes))
+        .collect();
+    // Events are collected per thread, so report them from this one.
+    for (blueprint, &geodes) in blueprints.iter().zip(&geodes) {
+        trace!(
+            "blueprint",
+            id = blueprint.id,
+            minutes = minutes,
+            geodes = geodes
+        );
+    }
+
+    geodes
+}
+
+fn blueprint<'a>() -> impl Parser<'a, Blueprint> {
+    let id = delimited(literal("Blueprint "), number(), literal(": "));
+    let ore = delimited(
+        literal("Each ore robot costs "),
+        number(),
+        literal(" ore. "),
+    );
+    let clay = delimited(
+        literal("Each clay robot costs "),
+        number(),
+        literal(" ore. "),
+    );
+    let obsidian = pair(
+        preceded(literal("Each obsidian robot costs "), number()),
+        delimited(literal(" ore and "), number(), literal(" clay. ")),
+    );
+    let geode = pair(
+        preceded(literal("Each geode robot costs "), number()),
+        delimited(literal(" ore and "), number(), literal(" obsidian.")),
+    );
+
+    parse::map(
+        pair(pair(id, pair(ore, clay)), pair(obsidian, geode)),
+        |((id, (ore, clay)), ((obsidian_ore, obsidian_clay), (geode_ore, geode_obsidian)))| {
+            Blueprint {
+                id,
+                costs: [
+                    [ore, 0, 0],
+                    [clay, 0, 0],
+                    [obsidian_ore, obsidian_clay, 0],
+                    [geode_ore, 0, geode_obsidian],
+                ],
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.
+Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.
+";
+
+    #[test]
+    fn parse_costs() {
+        let blueprints = Day19::parse(EXAMPLE).unwrap();
+
+        assert_eq!(blueprints.len(), 2);
+        assert_eq!(
+            blueprints[1],
+            Blueprint {
+                id: 2,
+                costs: [[2, 0, 0], [3, 0, 0], [3, 8, 0], [3, 0, 12]],
+            }
+        );
+        assert_eq!(blueprints[0].max_robots(), [4, 14, 7, u32::MAX]);
+    }
+
+    #[test]
+    fn quality_levels() {
+        let blueprints = Day19::parse(EXAMPLE).unwrap();
+
+        assert_eq!(max_geodes_each(&blueprints, 24), [9, 12]);
+        assert_eq!(Day19::part1(&blueprints).unwrap(), "33");
+    }
+
+    #[test]
+    fn longer() {
+        let blueprints = Day19::parse(EXAMPLE).unwrap();
+
+        assert_eq!(max_geodes_each(&blueprints, 32), [56, 62]);
+        assert_eq!(Day19::part2(&blueprints).unwrap(), "3472");
+    }
+
+    #[test]
+    fn cannot_afford_anything() {
+        let blueprints = Day19::parse(EXAMPLE).unwrap();
+
+        assert_eq!(max_geodes(&blueprints[0], 5), 0);
+        assert_eq!(max_geodes(&blueprints[0], 0), 0);
+    }
+
+    #[test]
+    fn bad_line() {
+        let line = EXAMPLE
+            .lines()
+            .next()
+            .unwrap()
+            .replace("14 clay", "14 obsidian");
+
+        let actual = Day19::parse(&line).unwrap_err().to_string();
+
+        assert!(actual.starts_with("line 1, column 109:"), "{actual}");
+    }
+}
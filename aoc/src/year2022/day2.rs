@@ -1,19 +1,39 @@
 use std::cmp::Ordering;
 
-type Error = Box<dyn std::error::Error>;
+use crate::{
+    solution::Solution,
+    trace,
+    util::parse::{self, any_char, lines, literal, pair, terminated, try_map, Parser},
+};
 
-pub fn run() -> Result<(), Error> {
-    let file = std::fs::read_to_string("input/day-2.txt")?;
-    let mut total: u32 = 0;
-    for line in file.lines() {
-        total += score_game_string(line)? as u32;
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<(Play, Play)>;
+
+    fn parse(file: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse::parse_all(lines(game()), file)?)
+    }
+
+    fn part1(games: &Self::Input) -> anyhow::Result<String> {
+        let mut total: u32 = 0;
+        for (index, &(p1, p2)) in games.iter().enumerate() {
+            let score = score_game(p1, p2);
+            trace!(
+                "round",
+                round = index + 1,
+                opponent = format!("{p1:?}"),
+                play = format!("{p2:?}"),
+                score = score,
+            );
+            total += score as u32;
+        }
+        Ok(total.to_string())
     }
-    println!("total: {total}");
-    Ok(())
 }
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
-enum Play {
+pub enum Play {
     Rock,
     Paper,
     Scissors,
@@ -35,14 +55,16 @@ impl PartialOrd for Play {
     }
 }
 
-fn parse_play(play: &str) -> Result<Play, String> {
-    let ok = match play {
-        "A" | "X" => Play::Rock,
-        "B" | "Y" => Play::Paper,
-        "C" | "Z" => Play::Scissors,
-        _ => return Err(format!("unknown: {play}")),
-    };
-    Ok(ok)
+fn play<'a>() -> impl Parser<'a, Play> {
+    try_map(any_char(), |play| {
+        let ok = match play {
+            'A' | 'X' => Play::Rock,
+            'B' | 'Y' => Play::Paper,
+            'C' | 'Z' => Play::Scissors,
+            _ => return Err("a play (A, B, C, X, Y or Z)"),
+        };
+        Ok(ok)
+    })
 }
 
 fn score_game(p1: Play, p2: Play) -> u8 {
@@ -59,20 +81,8 @@ fn score_game(p1: Play, p2: Play) -> u8 {
     play_score + game_score
 }
 
-fn score_game_string(game: &str) -> Result<u8, String> {
-    // let (p1, p2) = match game.split_once(' ') {
-    //     Some(ps) => ps,
-    //     None => return Err(format!("bad game: {game}")),
-    // };
-    let (p1, p2) = game
-        .split_once(' ')
-        .ok_or_else(|| format!("bad game: {game}"))?;
-    let p1 = parse_play(p1)?;
-    let p2 = match parse_play(p2) {
-        Ok(ok) => ok,
-        Err(err) => return Err(err),
-    };
-    Ok(score_game(p1, p2))
+fn game<'a>() -> impl Parser<'a, (Play, Play)> {
+    pair(terminated(play(), literal(" ")), play())
 }
 
 #[cfg(test)]
@@ -81,12 +91,12 @@ mod tests {
 
     #[test]
     fn can_parse_move() {
-        let actual = parse_play("A").unwrap();
+        let actual = parse::parse_all(play(), "A").unwrap();
         let expect = Play::Rock;
 
         assert_eq!(actual, expect);
 
-        let actual = parse_play("Y").unwrap();
+        let actual = parse::parse_all(play(), "Y").unwrap();
         let expect = Play::Paper;
 
         assert_eq!(actual, expect);
@@ -94,7 +104,7 @@ mod tests {
 
     #[test]
     fn cannot_parse_p1_bad_move() {
-        let actual = parse_play("Q");
+        let actual = parse::parse_all(play(), "Q");
         assert!(actual.is_err());
     }
 
@@ -113,8 +123,17 @@ mod tests {
 
     #[test]
     fn can_score_game_string() {
-        let actual = score_game_string("A X").unwrap();
+        let (p1, p2) = parse::parse_all(game(), "A X").unwrap();
+        let actual = score_game(p1, p2);
         let expect = 4;
         assert_eq!(actual, expect);
     }
+
+    #[test]
+    fn bad_game_has_position() {
+        let actual = parse::parse_all(lines(game()), "A X\nB Q\n").unwrap_err();
+
+        assert_eq!(actual.line, 2);
+        assert_eq!(actual.column, 3);
+    }
 }
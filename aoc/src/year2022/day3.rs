@@ -1,30 +1,84 @@
 use std::collections::{HashMap, HashSet};
 
-pub fn run() {
-    let file = match std::fs::read_to_string("input/day-3.txt") {
-        Ok(contents) => contents,
-        Err(error) => panic!("Could not open the file. Error: {}", error),
-    };
-
-    let scorer = Scorer::new();
-
-    let total: u32 = file
-        .lines()
-        .map(|line| {
-            let rucksack = split_to_rucksack(line);
-            let duplicate = rucksack.duplicate().expect("should have duplicate");
-
-            scorer
-                .score_letter(duplicate)
-                .expect("should be able to score letter") as u32
+use crate::{solution::Solution, trace};
+
+/// Scores letters with a `HashMap` lookup.
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Vec<Rucksack>;
+
+    fn parse(file: &str) -> anyhow::Result<Self::Input> {
+        Ok(file.lines().map(split_to_rucksack).collect())
+    }
+
+    fn part1(rucksacks: &Self::Input) -> anyhow::Result<String> {
+        let scorer = Scorer::new();
+
+        total_score(rucksacks, |letter| scorer.score_letter(letter))
+    }
+}
+
+/// Scores letters by finding them in the alphabet.
+pub struct LinearScan;
+
+impl Solution for LinearScan {
+    type Input = Vec<Rucksack>;
+
+    fn parse(file: &str) -> anyhow::Result<Self::Input> {
+        Day3::parse(file)
+    }
+
+    fn part1(rucksacks: &Self::Input) -> anyhow::Result<String> {
+        total_score(rucksacks, score_letter_linear_scan)
+    }
+}
+
+/// Scores letters with arithmetic on their ASCII value.
+pub struct Fast;
+
+impl Solution for Fast {
+    type Input = Vec<Rucksack>;
+
+    fn parse(file: &str) -> anyhow::Result<Self::Input> {
+        Day3::parse(file)
+    }
+
+    fn part1(rucksacks: &Self::Input) -> anyhow::Result<String> {
+        total_score(rucksacks, |letter| {
+            letter
+                .is_ascii_alphabetic()
+                .then(|| score_letter_fast(letter))
         })
-        .sum();
+    }
+}
+
+fn total_score(
+    rucksacks: &[Rucksack],
+    score_letter: impl Fn(char) -> Option<u8>,
+) -> anyhow::Result<String> {
+    let mut total: u32 = 0;
+    for (index, rucksack) in rucksacks.iter().enumerate() {
+        let duplicate = rucksack
+            .duplicate()
+            .ok_or_else(|| anyhow::anyhow!("no duplicate in {rucksack:?}"))?;
+        let score = score_letter(duplicate)
+            .ok_or_else(|| anyhow::anyhow!("cannot score letter {duplicate:?}"))?;
+        trace!(
+            "rucksack",
+            rucksack = index + 1,
+            duplicate = duplicate.to_string(),
+            score = score,
+        );
+
+        total += score as u32;
+    }
 
-    println!("Total score: {total}");
+    Ok(total.to_string())
 }
 
 #[derive(Debug, PartialEq, Eq)]
-struct Rucksack {
+pub struct Rucksack {
     first: String,
     second: String,
 }
@@ -87,9 +141,33 @@ fn score_letter_fast(letter: char) -> u8 {
     }
 }
 
-// #[cfg(test)]
+#[cfg(test)]
+#[allow(clippy::useless_vec, unused_variables)]
 mod tests {
     use super::*;
+    use crate::{solution::Part, Agreement};
+
+    const EXAMPLE: &str = "\
+vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw";
+
+    #[test]
+    fn variants_agree_on_example() {
+        let day = crate::days()
+            .find(|day| day.year == 2022 && day.number == 3)
+            .unwrap();
+
+        let actual = day.crosscheck(EXAMPLE).unwrap();
+
+        assert_eq!(
+            actual.parts[0],
+            (Part::One, Agreement::Agreed("157".to_string()))
+        );
+    }
 
     #[test]
     fn splits_rucksak() {
@@ -0,0 +1,157 @@
+use crate::{
+    solution::Solution,
+    trace,
+    util::{
+        interval::IntervalSet,
+        parse::{self, lines, literal, number, pair, terminated, Parser},
+    },
+};
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Vec<((u32, u32), (u32, u32))>;
+
+    fn parse(file: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse::parse_all(lines(assignment()), file)?)
+    }
+
+    fn part1(pairs: &Self::Input) -> anyhow::Result<String> {
+        let count = pairs
+            .iter()
+            .enumerate()
+            .filter(|&(index, &(first, second))| {
+                let contained = one_contains_other(first, second);
+                trace!(
+                    "pair",
+                    pair = index + 1,
+                    first = show(first),
+                    second = show(second),
+                    contained = contained,
+                );
+                contained
+            })
+            .count();
+
+        Ok(count.to_string())
+    }
+
+    fn part2(pairs: &Self::Input) -> anyhow::Result<String> {
+        let count = pairs
+            .iter()
+            .enumerate()
+            .filter(|&(index, &(first, second))| {
+                let overlaps = to_set(first).overlaps(&to_set(second));
+                trace!(
+                    "pair",
+                    pair = index + 1,
+                    first = show(first),
+                    second = show(second),
+                    overlaps = overlaps,
+                );
+                overlaps
+            })
+            .count();
+
+        Ok(count.to_string())
+    }
+}
+
+fn show((low, high): (u32, u32)) -> String {
+    format!("{low}-{high}")
+}
+
+fn one_contains_other(first: (u32, u32), second: (u32, u32)) -> bool {
+    is_contained(first, second) || is_contained(second, first)
+}
+
+fn is_contained(container: (u32, u32), subrange: (u32, u32)) -> bool {
+    container.0 <= subrange.0 && container.1 >= subrange.1
+}
+
+fn to_set((low, high): (u32, u32)) -> IntervalSet {
+    IntervalSet::from(low as i64..=high as i64)
+}
+
+fn range<'a>() -> impl Parser<'a, (u32, u32)> {
+    pair(terminated(number(), literal("-")), number())
+}
+
+fn assignment<'a>() -> impl Parser<'a, ((u32, u32), (u32, u32))> {
+    pair(terminated(range(), literal(",")), range())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let actual = parse::parse_all(assignment(), "23-27,13-67").unwrap();
+        let expected = ((23, 27), (13, 67));
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn bad_line() {
+        assert!(parse::parse_all(assignment(), "23-x,13-67").is_err());
+    }
+
+    #[test]
+    fn no_overlap() {
+        let overlap = is_contained((1, 3), (4, 6));
+        let expected = false;
+
+        assert_eq!(overlap, expected);
+    }
+
+    #[test]
+    fn container() {
+        let overlap = is_contained((1, 5), (2, 4));
+        let expected = true;
+
+        assert_eq!(overlap, expected);
+    }
+
+    #[test]
+    fn bottom_edge() {
+        let overlap = is_contained((1, 5), (1, 4));
+        let expected = true;
+
+        assert_eq!(overlap, expected);
+    }
+
+    #[test]
+    fn top_edge() {
+        let overlap = is_contained((1, 5), (3, 5));
+        let expected = true;
+
+        assert_eq!(overlap, expected);
+    }
+
+    #[test]
+    fn cross_over() {
+        let overlap = is_contained((1, 5), (3, 6));
+        let expected = false;
+
+        assert_eq!(overlap, expected);
+    }
+
+    #[test]
+    fn symmetry() {
+        let overlap = one_contains_other((3, 5), (1, 5));
+        let expected = true;
+
+        assert_eq!(overlap, expected);
+    }
+
+    #[test]
+    fn overlap() {
+        let overlaps = |first, second| to_set(first).overlaps(&to_set(second));
+
+        assert!(!overlaps((2, 4), (6, 8)));
+        assert!(overlaps((5, 7), (7, 9)));
+        assert!(overlaps((6, 6), (4, 6)));
+    }
+}
@@ -0,0 +1,334 @@
+use std::fmt;
+
+use crate::{
+    solution::Solution,
+    trace,
+    util::parse::{self, lines, literal, number, pair, preceded, Parser},
+};
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = (Stacks, Vec<Move>);
+
+    fn parse(file: &str) -> anyhow::Result<Self::Input> {
+        let Some((diagram, moves)) = file.split_once("\n\n") else {
+            anyhow::bail!("expected a blank line between the crates and the moves");
+        };
+
+        let stacks = parse_stacks(diagram)?;
+        let moves = parse::parse_all(lines(step()), moves).map_err(|mut error| {
+            // Line numbers should count from the top of the file.
+            error.line += diagram.lines().count() + 1;
+            error
+        })?;
+
+        Ok((stacks, moves))
+    }
+
+    fn part1((stacks, moves): &Self::Input) -> anyhow::Result<String> {
+        Ok(rearrange(stacks, moves, Crane::OneAtATime)?)
+    }
+
+    fn part2((stacks, moves): &Self::Input) -> anyhow::Result<String> {
+        Ok(rearrange(stacks, moves, Crane::Batch)?)
+    }
+}
+
+/// Crates, bottom first, in each numbered stack.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stacks(Vec<Vec<char>>);
+
+/// `move {count} from {from} to {to}`, with stacks numbered from 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    count: usize,
+    from: usize,
+    to: usize,
+}
+
+/// How the crane moves several crates from one stack to another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Crane {
+    /// The CrateMover 9000 moves crates one at a time, reversing their order.
+    OneAtATime,
+    /// The CrateMover 9001 moves them all at once, keeping their order.
+    Batch,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MoveError {
+    NoSuchStack {
+        stack: usize,
+        stacks: usize,
+    },
+    NotEnoughCrates {
+        stack: usize,
+        wanted: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoveError::NoSuchStack { stack, stacks } => {
+                write!(f, "no stack {stack}, there are {stacks}")
+            }
+            MoveError::NotEnoughCrates {
+                stack,
+                wanted,
+                found,
+            } => write!(
+                f,
+                "cannot take {wanted} crates from stack {stack}, it has {found}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MoveError {}
+
+/// A move that could not be made, and which one it was.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidMove {
+    pub index: usize,
+    pub error: MoveError,
+}
+
+impl fmt::Display for InvalidMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "move {}: {}", self.index + 1, self.error)
+    }
+}
+
+impl std::error::Error for InvalidMove {}
+
+impl Stacks {
+    fn stack(&self, stack: usize) -> Result<&Vec<char>, MoveError> {
+        stack
+            .checked_sub(1)
+            .and_then(|index| self.0.get(index))
+            .ok_or(MoveError::NoSuchStack {
+                stack,
+                stacks: self.0.len(),
+            })
+    }
+
+    fn apply(&mut self, step: Move, crane: Crane) -> Result<(), MoveError> {
+        let found = self.stack(step.from)?.len();
+        self.stack(step.to)?;
+        if found < step.count {
+            return Err(MoveError::NotEnoughCrates {
+                stack: step.from,
+                wanted: step.count,
+                found,
+            });
+        }
+
+        let mut lifted = self.0[step.from - 1].split_off(found - step.count);
+        if crane == Crane::OneAtATime {
+            lifted.reverse();
+        }
+        self.0[step.to - 1].extend(lifted);
+
+        Ok(())
+    }
+
+    /// The top crate of each stack, skipping empty ones.
+    fn tops(&self) -> String {
+        self.0.iter().filter_map(|stack| stack.last()).collect()
+    }
+}
+
+fn rearrange(stacks: &Stacks, moves: &[Move], crane: Crane) -> Result<String, InvalidMove> {
+    let mut stacks = stacks.clone();
+    for (index, &step) in moves.iter().enumerate() {
+        stacks
+            .apply(step, crane)
+            .map_err(|error| InvalidMove { index, error })?;
+        trace!("move", step = index + 1, tops = stacks.tops());
+    }
+
+    Ok(stacks.tops())
+}
+
+/// Parse a drawing like
+///
+/// ```text
+///     [D]
+/// [N] [C]
+/// [Z] [M] [P]
+///  1   2   3
+/// ```
+///
+/// where each crate sits above the number of its stack.
+fn parse_stacks(diagram: &str) -> anyhow::Result<Stacks> {
+    let mut rows: Vec<_> = diagram.lines().collect();
+    let Some(numbers) = rows.pop() else {
+        anyhow::bail!("line 1: expected a drawing of the crates");
+    };
+
+    // Every stack number should be in the same column as its crates.
+    let columns: Vec<_> = numbers
+        .char_indices()
+        .filter(|(_, c)| !c.is_whitespace())
+        .map(|(column, _)| column)
+        .collect();
+    for (index, &column) in columns.iter().enumerate() {
+        let expected = char::from_digit(index as u32 + 1, 10);
+        if numbers[column..].chars().next() != expected {
+            anyhow::bail!(
+                "line {}, column {}: expected stack number {}",
+                rows.len() + 1,
+                column + 1,
+                index + 1
+            );
+        }
+    }
+
+    let mut stacks = vec![Vec::new(); columns.len()];
+    for (line, row) in rows.iter().enumerate().rev() {
+        for (column, c) in row.char_indices() {
+            if !c.is_ascii_uppercase() {
+                continue;
+            }
+            let Some(stack) = columns.iter().position(|&number| number == column) else {
+                anyhow::bail!(
+                    "line {}, column {}: crate {c} is not above a stack",
+                    line + 1,
+                    column + 1
+                );
+            };
+            stacks[stack].push(c);
+        }
+    }
+
+    Ok(Stacks(stacks))
+}
+
+fn step<'a>() -> impl Parser<'a, Move> {
+    let count = preceded(literal("move "), number());
+    let from = preceded(literal(" from "), number());
+    let to = preceded(literal(" to "), number());
+
+    parse::map(pair(count, pair(from, to)), |(count, (from, to))| Move {
+        count,
+        from,
+        to,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "    [D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2
+";
+
+    #[test]
+    fn parse_example() {
+        let (stacks, moves) = Day5::parse(EXAMPLE).unwrap();
+
+        let expected = Stacks(vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']]);
+        assert_eq!(stacks, expected);
+
+        assert_eq!(moves.len(), 4);
+        assert_eq!(
+            moves[1],
+            Move {
+                count: 3,
+                from: 1,
+                to: 3
+            }
+        );
+    }
+
+    #[test]
+    fn one_at_a_time() {
+        let input = Day5::parse(EXAMPLE).unwrap();
+
+        assert_eq!(Day5::part1(&input).unwrap(), "CMZ");
+    }
+
+    #[test]
+    fn batch() {
+        let input = Day5::parse(EXAMPLE).unwrap();
+
+        assert_eq!(Day5::part2(&input).unwrap(), "MCD");
+    }
+
+    #[test]
+    fn empty_stack() {
+        let mut stacks = Stacks(vec![vec!['A'], vec![]]);
+        let step = Move {
+            count: 1,
+            from: 2,
+            to: 1,
+        };
+
+        let actual = stacks.apply(step, Crane::OneAtATime);
+        let expected = Err(MoveError::NotEnoughCrates {
+            stack: 2,
+            wanted: 1,
+            found: 0,
+        });
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn bad_stack_index() {
+        let mut stacks = Stacks(vec![vec!['A'], vec![]]);
+
+        for (from, to) in [(0, 1), (1, 3)] {
+            let step = Move { count: 1, from, to };
+            let actual = stacks.apply(step, Crane::Batch);
+
+            assert!(matches!(actual, Err(MoveError::NoSuchStack { .. })));
+        }
+        assert_eq!(stacks, Stacks(vec![vec!['A'], vec![]]));
+    }
+
+    #[test]
+    fn invalid_move_is_numbered() {
+        let (stacks, _) = Day5::parse(EXAMPLE).unwrap();
+        let moves = [
+            Move {
+                count: 1,
+                from: 3,
+                to: 1,
+            },
+            Move {
+                count: 1,
+                from: 3,
+                to: 1,
+            },
+        ];
+
+        let actual = rearrange(&stacks, &moves, Crane::OneAtATime).unwrap_err();
+
+        assert_eq!(actual.index, 1);
+        assert_eq!(
+            actual.to_string(),
+            "move 2: cannot take 1 crates from stack 3, it has 0"
+        );
+    }
+
+    #[test]
+    fn bad_move_line_counts_from_top() {
+        let file = EXAMPLE.replace("move 2 from 2 to 1", "move 2 from x to 1");
+
+        let actual = Day5::parse(&file).unwrap_err().to_string();
+
+        assert!(actual.starts_with("line 8, column 13:"), "{actual}");
+    }
+}
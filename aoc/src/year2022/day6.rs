@@ -0,0 +1,213 @@
+//! Finding the first run of distinct characters in a signal. [`SlidingWindow`]
+//! does it in one pass; [`Naive`] is kept to compare against, e.g. with
+//! `aoc crosscheck --day 6`, which checks they agree and times each, or with
+//! the ignored `benchmark` test.
+
+use std::collections::HashSet;
+
+use crate::{solution::Solution, trace};
+
+const START_OF_PACKET: usize = 4;
+const START_OF_MESSAGE: usize = 14;
+
+/// Keeps a count of each byte in the window, updated as it slides.
+pub struct SlidingWindow;
+
+impl Solution for SlidingWindow {
+    type Input = Vec<u8>;
+
+    fn parse(file: &str) -> anyhow::Result<Self::Input> {
+        Ok(file.trim().as_bytes().to_vec())
+    }
+
+    fn part1(signal: &Self::Input) -> anyhow::Result<String> {
+        answer(find_marker(signal, START_OF_PACKET), START_OF_PACKET)
+    }
+
+    fn part2(signal: &Self::Input) -> anyhow::Result<String> {
+        answer(find_marker(signal, START_OF_MESSAGE), START_OF_MESSAGE)
+    }
+}
+
+/// Builds a `HashSet` of every window.
+pub struct Naive;
+
+impl Solution for Naive {
+    type Input = Vec<u8>;
+
+    fn parse(file: &str) -> anyhow::Result<Self::Input> {
+        SlidingWindow::parse(file)
+    }
+
+    fn part1(signal: &Self::Input) -> anyhow::Result<String> {
+        answer(find_marker_naive(signal, START_OF_PACKET), START_OF_PACKET)
+    }
+
+    fn part2(signal: &Self::Input) -> anyhow::Result<String> {
+        answer(
+            find_marker_naive(signal, START_OF_MESSAGE),
+            START_OF_MESSAGE,
+        )
+    }
+}
+
+fn answer(marker: Option<usize>, size: usize) -> anyhow::Result<String> {
+    match marker {
+        Some(end) => Ok(end.to_string()),
+        None => anyhow::bail!("no {size} distinct characters in a row"),
+    }
+}
+
+/// How many bytes have been read when the last `size` of them are first all
+/// different, in time linear in the length of `signal`.
+pub fn find_marker(signal: &[u8], size: usize) -> Option<usize> {
+    if size == 0 {
+        return Some(0);
+    }
+
+    let mut counts = [0u32; 256];
+    // How many byte values appear more than once in the window.
+    let mut repeated = 0;
+
+    for (index, &byte) in signal.iter().enumerate() {
+        counts[byte as usize] += 1;
+        if counts[byte as usize] == 2 {
+            repeated += 1;
+        }
+
+        if let Some(leaving) = index.checked_sub(size) {
+            let leaving = signal[leaving] as usize;
+            if counts[leaving] == 2 {
+                repeated -= 1;
+            }
+            counts[leaving] -= 1;
+        }
+
+        if index + 1 >= size && repeated == 0 {
+            trace!(
+                "marker",
+                size = size,
+                end = index + 1,
+                window = String::from_utf8_lossy(&signal[index + 1 - size..=index]).into_owned(),
+            );
+            return Some(index + 1);
+        }
+    }
+
+    None
+}
+
+fn find_marker_naive(signal: &[u8], size: usize) -> Option<usize> {
+    if size == 0 {
+        return Some(0);
+    }
+
+    signal
+        .windows(size)
+        .position(|window| window.iter().collect::<HashSet<_>>().len() == size)
+        .map(|start| start + size)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::*;
+
+    const EXAMPLES: [(&str, usize, usize); 5] = [
+        ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+        ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+        ("nppdvjthqldpwncqszvftbjwlz", 6, 23),
+        ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+        ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+    ];
+
+    #[test]
+    fn start_of_packet() {
+        for (signal, expected, _) in EXAMPLES {
+            assert_eq!(
+                find_marker(signal.as_bytes(), 4),
+                Some(expected),
+                "{signal}"
+            );
+        }
+    }
+
+    #[test]
+    fn start_of_message() {
+        for (signal, _, expected) in EXAMPLES {
+            assert_eq!(
+                find_marker(signal.as_bytes(), 14),
+                Some(expected),
+                "{signal}"
+            );
+        }
+    }
+
+    #[test]
+    fn other_window_sizes() {
+        assert_eq!(find_marker(b"aab", 1), Some(1));
+        assert_eq!(find_marker(b"aab", 2), Some(3));
+        assert_eq!(find_marker(b"aab", 3), None);
+        assert_eq!(find_marker(b"ab", 5), None);
+        assert_eq!(find_marker(b"", 0), Some(0));
+    }
+
+    /// `length` pseudo-random letters from the first `letters` of the
+    /// alphabet.
+    fn signal(length: usize, letters: u8) -> Vec<u8> {
+        let mut seed: u32 = 7;
+        (0..length)
+            .map(|_| {
+                seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+                b'a' + (seed >> 16) as u8 % letters
+            })
+            .collect()
+    }
+
+    /// The quickest of a few runs of `find` over the whole of `signal`.
+    fn fastest(find: fn(&[u8], usize) -> Option<usize>, signal: &[u8], size: usize) -> Duration {
+        (0..5)
+            .map(|_| {
+                let start = Instant::now();
+                assert_eq!(find(std::hint::black_box(signal), size), None);
+                start.elapsed()
+            })
+            .min()
+            .unwrap()
+    }
+
+    #[test]
+    fn matches_naive() {
+        // A short alphabet so that markers are rare and windows slide a lot.
+        let signal = signal(5000, 8);
+
+        for size in 0..=9 {
+            assert_eq!(
+                find_marker(&signal, size),
+                find_marker_naive(&signal, size),
+                "size {size}"
+            );
+        }
+    }
+
+    /// Run with `cargo test --release -- --ignored`. Three letters never
+    /// make a marker, so each search reads all of the signal: the naive
+    /// search slows with the window, the sliding one does not.
+    #[test]
+    #[ignore]
+    fn benchmark() {
+        let signal = signal(1_000_000, 3);
+
+        let window = [4, 14].map(|size| fastest(find_marker, &signal, size));
+        let naive = [4, 14].map(|size| fastest(find_marker_naive, &signal, size));
+        println!("sizes 4 and 14: window {window:?}, naive {naive:?}");
+
+        assert!(window[1] < window[0] * 2, "window {window:?}");
+        assert!(naive[1] > naive[0] * 2, "naive {naive:?}");
+        assert!(
+            naive[1] > window[1] * 10,
+            "window {window:?}, naive {naive:?}"
+        );
+    }
+}
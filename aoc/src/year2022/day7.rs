@@ -0,0 +1,335 @@
+use std::{collections::BTreeMap, fmt};
+
+use crate::{solution::Solution, trace};
+
+const SMALL_DIRECTORY: u64 = 100_000;
+const DISK_SIZE: u64 = 70_000_000;
+const SPACE_NEEDED: u64 = 30_000_000;
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Filesystem;
+
+    fn parse(file: &str) -> anyhow::Result<Self::Input> {
+        Filesystem::replay(file)
+    }
+
+    fn part1(filesystem: &Self::Input) -> anyhow::Result<String> {
+        let total: u64 = filesystem
+            .sizes()
+            .into_iter()
+            .filter(|&size| size <= SMALL_DIRECTORY)
+            .sum();
+
+        Ok(total.to_string())
+    }
+
+    fn part2(filesystem: &Self::Input) -> anyhow::Result<String> {
+        let sizes = filesystem.sizes();
+        let free = DISK_SIZE.saturating_sub(sizes[ROOT]);
+        let Some(missing) = SPACE_NEEDED.checked_sub(free) else {
+            return Ok("0".to_string());
+        };
+
+        let (dir, size) = sizes
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, size)| size >= missing)
+            .min_by_key(|&(_, size)| size)
+            .expect("the root frees enough if anything does");
+        trace!("delete", path = filesystem.path(dir), size = size);
+
+        Ok(size.to_string())
+    }
+}
+
+/// The index of `/` among the directories.
+const ROOT: usize = 0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Entry {
+    /// An index into the filesystem's directories.
+    Dir(usize),
+    /// A file and its size.
+    File(u64),
+}
+
+#[derive(Debug, Default)]
+struct Dir {
+    name: String,
+    parent: Option<usize>,
+    children: BTreeMap<String, Entry>,
+}
+
+/// The directory tree seen through a terminal transcript, with every
+/// directory stored after its parent.
+#[derive(Debug)]
+pub struct Filesystem {
+    dirs: Vec<Dir>,
+}
+
+impl Filesystem {
+    /// Rebuild the tree from the `$ cd` and `$ ls` commands in `transcript`,
+    /// which starts at `/`.
+    pub fn replay(transcript: &str) -> anyhow::Result<Self> {
+        let mut filesystem = Filesystem {
+            dirs: vec![Dir::default()],
+        };
+        let mut current = ROOT;
+        let mut listing = false;
+
+        for (index, line) in transcript.lines().enumerate() {
+            let line_number = index + 1;
+            let mut words = line.split_whitespace();
+
+            match (words.next(), words.next(), words.next(), words.next()) {
+                (Some("$"), Some("cd"), Some(target), None) => {
+                    listing = false;
+                    current = match target {
+                        "/" => ROOT,
+                        ".." => match filesystem.dirs[current].parent {
+                            Some(parent) => parent,
+                            None => anyhow::bail!("line {line_number}: cannot cd .. from /"),
+                        },
+                        name => match filesystem.dirs[current].children.get(name) {
+                            Some(&Entry::Dir(dir)) => dir,
+                            Some(Entry::File(_)) => {
+                                anyhow::bail!("line {line_number}: cannot cd into file {name}")
+                            }
+                            None => anyhow::bail!(
+                                "line {line_number}: cd into unknown directory {name} in {}",
+                                filesystem.path(current)
+                            ),
+                        },
+                    };
+                }
+                (Some("$"), Some("ls"), None, None) => listing = true,
+                (Some("$"), command, _, _) => anyhow::bail!(
+                    "line {line_number}: unknown command {:?}",
+                    command.unwrap_or_default()
+                ),
+                (Some(_), Some(_), None, None) if !listing => {
+                    anyhow::bail!("line {line_number}: expected a command, found {line:?}")
+                }
+                (Some("dir"), Some(name), None, None) => {
+                    filesystem.add_dir(current, name);
+                }
+                (Some(size), Some(name), None, None) => {
+                    let Ok(size) = size.parse() else {
+                        anyhow::bail!("line {line_number}: expected a size or dir, found {size:?}");
+                    };
+                    filesystem.dirs[current]
+                        .children
+                        .insert(name.to_string(), Entry::File(size));
+                }
+                _ => anyhow::bail!("line {line_number}: cannot read {line:?}"),
+            }
+        }
+
+        Ok(filesystem)
+    }
+
+    fn add_dir(&mut self, parent: usize, name: &str) {
+        if self.dirs[parent].children.contains_key(name) {
+            return;
+        }
+
+        let dir = self.dirs.len();
+        self.dirs.push(Dir {
+            name: name.to_string(),
+            parent: Some(parent),
+            children: BTreeMap::new(),
+        });
+        self.dirs[parent]
+            .children
+            .insert(name.to_string(), Entry::Dir(dir));
+    }
+
+    /// Find the entry at an absolute path like `/a/e`.
+    pub fn lookup(&self, path: &str) -> Option<Entry> {
+        let path = path.strip_prefix('/')?;
+
+        path.split('/')
+            .filter(|name| !name.is_empty())
+            .try_fold(Entry::Dir(ROOT), |entry, name| match entry {
+                Entry::Dir(dir) => self.dirs[dir].children.get(name).copied(),
+                Entry::File(_) => None,
+            })
+    }
+
+    /// The absolute path of a directory.
+    pub fn path(&self, dir: usize) -> String {
+        let mut names = Vec::new();
+        let mut current = dir;
+        while let Some(parent) = self.dirs[current].parent {
+            names.push(self.dirs[current].name.as_str());
+            current = parent;
+        }
+        names.reverse();
+
+        format!("/{}", names.join("/"))
+    }
+
+    /// The total size of the files in each directory and everything below
+    /// it, indexed like the directories.
+    pub fn sizes(&self) -> Vec<u64> {
+        let mut sizes: Vec<u64> = self
+            .dirs
+            .iter()
+            .map(|dir| {
+                dir.children
+                    .values()
+                    .map(|entry| match entry {
+                        Entry::File(size) => size,
+                        Entry::Dir(_) => &0,
+                    })
+                    .sum()
+            })
+            .collect();
+
+        // Children come after their parents, so adding from the back
+        // finishes each directory before it is added to its parent.
+        for dir in (1..self.dirs.len()).rev() {
+            let parent = self.dirs[dir].parent.expect("only the root has no parent");
+            sizes[parent] += sizes[dir];
+        }
+
+        sizes
+    }
+
+    fn write_dir(&self, f: &mut fmt::Formatter<'_>, dir: usize, depth: usize) -> fmt::Result {
+        for (name, entry) in &self.dirs[dir].children {
+            let indent = "  ".repeat(depth);
+            match entry {
+                Entry::Dir(child) => {
+                    writeln!(f, "{indent}- {name} (dir)")?;
+                    self.write_dir(f, *child, depth + 1)?;
+                }
+                Entry::File(size) => writeln!(f, "{indent}- {name} (file, size={size})")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The tree as the puzzle draws it, one entry per line.
+impl fmt::Display for Filesystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "- / (dir)")?;
+        self.write_dir(f, ROOT, 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k
+";
+
+    #[test]
+    fn small_directories() {
+        let filesystem = Day7::parse(EXAMPLE).unwrap();
+
+        assert_eq!(Day7::part1(&filesystem).unwrap(), "95437");
+    }
+
+    #[test]
+    fn directory_to_delete() {
+        let filesystem = Day7::parse(EXAMPLE).unwrap();
+
+        assert_eq!(Day7::part2(&filesystem).unwrap(), "24933642");
+    }
+
+    #[test]
+    fn lookup() {
+        let filesystem = Day7::parse(EXAMPLE).unwrap();
+        let sizes = filesystem.sizes();
+
+        let Some(Entry::Dir(e)) = filesystem.lookup("/a/e") else {
+            panic!("/a/e is a directory");
+        };
+        assert_eq!(sizes[e], 584);
+        assert_eq!(filesystem.path(e), "/a/e");
+        assert_eq!(filesystem.lookup("/d/k"), Some(Entry::File(7214296)));
+        assert_eq!(filesystem.lookup("/"), Some(Entry::Dir(ROOT)));
+        assert_eq!(filesystem.lookup("/a/nope"), None);
+        assert_eq!(filesystem.lookup("/b.txt/x"), None);
+    }
+
+    #[test]
+    fn pretty_print() {
+        let filesystem = Day7::parse(EXAMPLE).unwrap();
+
+        let actual = filesystem.to_string();
+        let expected = "- / (dir)
+  - a (dir)
+    - e (dir)
+      - i (file, size=584)
+    - f (file, size=29116)
+    - g (file, size=2557)
+    - h.lst (file, size=62596)
+  - b.txt (file, size=14848514)
+  - c.dat (file, size=8504156)
+  - d (dir)
+    - d.ext (file, size=5626152)
+    - d.log (file, size=8033020)
+    - j (file, size=4060174)
+    - k (file, size=7214296)
+";
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn unknown_directory() {
+        let transcript = EXAMPLE.replace("$ cd d", "$ cd x");
+
+        let actual = Day7::parse(&transcript).unwrap_err().to_string();
+
+        assert_eq!(actual, "line 18: cd into unknown directory x in /");
+    }
+
+    #[test]
+    fn transcript_errors() {
+        let cases = [
+            ("$ cd ..", "line 1: cannot cd .. from /"),
+            ("$ rm -rf /", "line 1: unknown command \"rm\""),
+            ("dir a", "line 1: expected a command, found \"dir a\""),
+            (
+                "$ ls\nbig a",
+                "line 2: expected a size or dir, found \"big\"",
+            ),
+            ("$ ls\n12 b\n$ cd b", "line 3: cannot cd into file b"),
+        ];
+
+        for (transcript, expected) in cases {
+            let actual = Day7::parse(transcript).unwrap_err().to_string();
+            assert_eq!(actual, expected);
+        }
+    }
+}
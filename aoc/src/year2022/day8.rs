@@ -0,0 +1,187 @@
+use crate::{
+    solution::Solution,
+    trace,
+    util::grid::{Grid, Point},
+};
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Grid<u8>;
+
+    fn parse(file: &str) -> anyhow::Result<Self::Input> {
+        Grid::parse(file, |c| match c.to_digit(10) {
+            Some(height) => Ok(height as u8),
+            None => anyhow::bail!("expected a tree height, found {c:?}"),
+        })
+    }
+
+    fn part1(heights: &Self::Input) -> anyhow::Result<String> {
+        let survey = Survey::new(heights);
+        let visible = survey
+            .visible
+            .iter()
+            .filter(|(_, &visible)| visible)
+            .count();
+
+        Ok(visible.to_string())
+    }
+
+    fn part2(heights: &Self::Input) -> anyhow::Result<String> {
+        let survey = Survey::new(heights);
+        let Some((tree, score)) = survey.scenic.iter().max_by_key(|(_, &score)| score) else {
+            anyhow::bail!("there are no trees");
+        };
+        trace!("best", tree = tree.to_string(), score = *score);
+
+        Ok(score.to_string())
+    }
+}
+
+/// What can be seen from outside the forest and from each tree.
+pub struct Survey {
+    /// Whether each tree can be seen from outside along a row or column.
+    pub visible: Grid<bool>,
+    /// The product of how far each tree can see in the four directions.
+    pub scenic: Grid<usize>,
+}
+
+impl Survey {
+    /// Look along every row and column in both directions, once each, so the
+    /// work is linear in the number of trees.
+    pub fn new(heights: &Grid<u8>) -> Self {
+        let mut visible = heights.map(|_| false);
+        let mut scenic = heights.map(|_| 1);
+
+        for line in lines(heights.width(), heights.height()) {
+            let mut tallest = None;
+            // Trees not yet blocked by a taller one later in the line, as
+            // (position along the line, height), tallest at the bottom.
+            let mut blockers: Vec<(usize, u8)> = Vec::new();
+
+            for (index, &point) in line.iter().enumerate() {
+                let height = heights[point];
+
+                if tallest.is_none_or(|tallest| height > tallest) {
+                    visible[point] = true;
+                    tallest = Some(height);
+                }
+
+                while blockers.last().is_some_and(|&(_, other)| other < height) {
+                    blockers.pop();
+                }
+                let distance = match blockers.last() {
+                    Some(&(blocker, _)) => index - blocker,
+                    None => index,
+                };
+                scenic[point] *= distance;
+                blockers.push((index, height));
+            }
+        }
+
+        Self { visible, scenic }
+    }
+}
+
+/// Every row and column, walked in both directions.
+fn lines(width: usize, height: usize) -> impl Iterator<Item = Vec<Point>> {
+    let rows = (0..height).map(move |y| (0..width).map(|x| Point::new(x, y)).collect::<Vec<_>>());
+    let columns =
+        (0..width).map(move |x| (0..height).map(|y| Point::new(x, y)).collect::<Vec<_>>());
+
+    rows.chain(columns).flat_map(|line| {
+        let reversed = line.iter().rev().copied().collect();
+        [line, reversed]
+    })
+}
+
+/// The forest with visible trees drawn as their height and hidden ones as
+/// `.`.
+pub fn render(heights: &Grid<u8>, visible: &Grid<bool>) -> String {
+    let mut drawing = heights.map(|&height| char::from(b'0' + height));
+    for (point, &visible) in visible.iter() {
+        if !visible {
+            drawing[point] = '.';
+        }
+    }
+
+    drawing.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::grid::ORTHOGONAL;
+
+    const EXAMPLE: &str = "30373
+25512
+65332
+33549
+35390
+";
+
+    #[test]
+    fn visible_trees() {
+        let heights = Day8::parse(EXAMPLE).unwrap();
+
+        assert_eq!(Day8::part1(&heights).unwrap(), "21");
+    }
+
+    #[test]
+    fn scenic_score() {
+        let heights = Day8::parse(EXAMPLE).unwrap();
+        let survey = Survey::new(&heights);
+
+        assert_eq!(survey.scenic[Point::new(2, 1)], 4);
+        assert_eq!(survey.scenic[Point::new(2, 3)], 8);
+        assert_eq!(Day8::part2(&heights).unwrap(), "8");
+    }
+
+    #[test]
+    fn scenic_score_matches_looking_from_each_tree() {
+        let heights = Day8::parse(EXAMPLE).unwrap();
+        let survey = Survey::new(&heights);
+
+        for point in heights.points() {
+            let expected: usize = ORTHOGONAL
+                .into_iter()
+                .map(|step| {
+                    let ray: Vec<_> = heights.ray(point, step).collect();
+                    match ray
+                        .iter()
+                        .position(|&other| heights[other] >= heights[point])
+                    {
+                        Some(blocker) => blocker + 1,
+                        None => ray.len(),
+                    }
+                })
+                .product();
+            assert_eq!(survey.scenic[point], expected, "{point}");
+        }
+    }
+
+    #[test]
+    fn render_hides_trees() {
+        let heights = Day8::parse(EXAMPLE).unwrap();
+        let survey = Survey::new(&heights);
+
+        let actual = render(&heights, &survey.visible);
+        let expected = "30373
+255.2
+65.32
+3.5.9
+35390";
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn rejects_non_digits() {
+        let actual = Day8::parse("123\n4x6\n").unwrap_err().to_string();
+
+        assert_eq!(
+            actual,
+            "line 2, column 2: expected a tree height, found 'x'"
+        );
+    }
+}
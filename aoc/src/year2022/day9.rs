@@ -0,0 +1,412 @@
+use std::{collections::HashSet, fmt};
+
+use crate::{
+    solution::Solution,
+    trace,
+    util::parse::{self, alt, lines, literal, map, number, pair, preceded, Parser},
+};
+
+/// The largest frames worth tracing, which fit on a terminal.
+const FRAME_WIDTH: i32 = 80;
+const FRAME_HEIGHT: i32 = 40;
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Vec<Move>;
+
+    fn parse(file: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse::parse_all(lines(head_move()), file)?)
+    }
+
+    fn part1(moves: &Self::Input) -> anyhow::Result<String> {
+        Ok(tail_positions(moves, 2)?.to_string())
+    }
+
+    fn part2(moves: &Self::Input) -> anyhow::Result<String> {
+        Ok(tail_positions(moves, 10)?.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    /// One step this way, with `y` growing downwards.
+    fn step(self) -> Knot {
+        match self {
+            Direction::Up => Knot { x: 0, y: -1 },
+            Direction::Down => Knot { x: 0, y: 1 },
+            Direction::Left => Knot { x: -1, y: 0 },
+            Direction::Right => Knot { x: 1, y: 0 },
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let letter = match self {
+            Direction::Up => "U",
+            Direction::Down => "D",
+            Direction::Left => "L",
+            Direction::Right => "R",
+        };
+        f.write_str(letter)
+    }
+}
+
+/// Move the head `count` steps in `direction`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    direction: Direction,
+    count: usize,
+}
+
+/// A knot's position relative to where the rope starts, which is unbounded
+/// in every direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Knot {
+    x: i32,
+    y: i32,
+}
+
+/// A rope of knots, head first, each one following the one before it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rope {
+    knots: Vec<Knot>,
+}
+
+impl Rope {
+    /// A rope of `length` knots, all at the start. It needs at least a head.
+    pub fn new(length: usize) -> anyhow::Result<Self> {
+        if length == 0 {
+            anyhow::bail!("a rope needs at least one knot");
+        }
+
+        Ok(Self {
+            knots: vec![Knot::default(); length],
+        })
+    }
+
+    pub fn tail(&self) -> Knot {
+        *self.knots.last().expect("a rope has at least one knot")
+    }
+
+    /// Move the head one step and let every other knot catch up.
+    pub fn step(&mut self, direction: Direction) {
+        let step = direction.step();
+        self.knots[0].x += step.x;
+        self.knots[0].y += step.y;
+
+        for index in 1..self.knots.len() {
+            let leader = self.knots[index - 1];
+            let knot = &mut self.knots[index];
+            let (dx, dy) = (leader.x - knot.x, leader.y - knot.y);
+            if dx.abs() <= 1 && dy.abs() <= 1 {
+                // Touching knots stay put, and so does everything behind them.
+                break;
+            }
+            knot.x += dx.signum();
+            knot.y += dy.signum();
+        }
+    }
+
+    /// What is at `position`: the frontmost knot, `s` for the start, or `.`.
+    fn draw(&self, position: Knot) -> char {
+        match self.knots.iter().position(|&knot| knot == position) {
+            Some(0) => 'H',
+            Some(_) if self.knots.len() == 2 => 'T',
+            Some(index) => char::from_digit(index as u32, 36).unwrap_or('#'),
+            None if position == Knot::default() => 's',
+            None => '.',
+        }
+    }
+}
+
+/// How many places the tail of a rope of `length` knots visits.
+pub fn tail_positions(moves: &[Move], length: usize) -> anyhow::Result<usize> {
+    let mut rope = Rope::new(length)?;
+    let mut visited = HashSet::from([rope.tail()]);
+    trace_frames(moves, length)?;
+
+    for (index, &Move { direction, count }) in moves.iter().enumerate() {
+        for _ in 0..count {
+            rope.step(direction);
+            visited.insert(rope.tail());
+        }
+        trace!(
+            "move",
+            step = index + 1,
+            length = length,
+            tail = format!("({}, {})", rope.tail().x, rope.tail().y),
+            visited = visited.len(),
+        );
+    }
+
+    Ok(visited.len())
+}
+
+/// The rope after every step of every move.
+fn simulate(moves: &[Move], length: usize) -> anyhow::Result<Vec<Vec<Rope>>> {
+    let mut rope = Rope::new(length)?;
+
+    Ok(moves
+        .iter()
+        .map(|&Move { direction, count }| {
+            (0..count)
+                .map(|_| {
+                    rope.step(direction);
+                    rope.clone()
+                })
+                .collect()
+        })
+        .collect())
+}
+
+/// The corners of the smallest window that fits every knot of every rope,
+/// and the start.
+fn window(states: &[Vec<Rope>]) -> (Knot, Knot) {
+    let (mut min, mut max) = (Knot::default(), Knot::default());
+    for knot in states.iter().flatten().flat_map(|rope| &rope.knots) {
+        min = Knot {
+            x: min.x.min(knot.x),
+            y: min.y.min(knot.y),
+        };
+        max = Knot {
+            x: max.x.max(knot.x),
+            y: max.y.max(knot.y),
+        };
+    }
+
+    (min, max)
+}
+
+fn draw_frames(moves: &[Move], states: &[Vec<Rope>], (min, max): (Knot, Knot)) -> Vec<String> {
+    moves
+        .iter()
+        .zip(states)
+        .map(|(step, ropes)| {
+            let grids: Vec<String> = ropes
+                .iter()
+                .map(|rope| {
+                    let rows: Vec<String> = (min.y..=max.y)
+                        .map(|y| (min.x..=max.x).map(|x| rope.draw(Knot { x, y })).collect())
+                        .collect();
+                    rows.join("\n")
+                })
+                .collect();
+            format!(
+                "== {} {} ==\n\n{}",
+                step.direction,
+                step.count,
+                grids.join("\n\n")
+            )
+        })
+        .collect()
+}
+
+/// Draw the rope after every step, one frame for each move, like the puzzle
+/// does, for stepping through small inputs by eye. All frames share the
+/// smallest window that fits every knot at every step.
+pub fn frames(moves: &[Move], length: usize) -> anyhow::Result<Vec<String>> {
+    let states = simulate(moves, length)?;
+
+    Ok(draw_frames(moves, &states, window(&states)))
+}
+
+/// Emit each of the [`frames`] as a `frame` event when tracing, unless the
+/// rope wanders too far to draw.
+fn trace_frames(moves: &[Move], length: usize) -> anyhow::Result<()> {
+    if !trace::enabled() {
+        return Ok(());
+    }
+
+    let states = simulate(moves, length)?;
+    let (min, max) = window(&states);
+    if max.x - min.x >= FRAME_WIDTH || max.y - min.y >= FRAME_HEIGHT {
+        return Ok(());
+    }
+    for (index, frame) in draw_frames(moves, &states, (min, max))
+        .into_iter()
+        .enumerate()
+    {
+        trace!("frame", step = index + 1, length = length, frame = frame);
+    }
+
+    Ok(())
+}
+
+fn direction<'a>() -> impl Parser<'a, Direction> {
+    alt((
+        map(literal("U"), |_| Direction::Up),
+        map(literal("D"), |_| Direction::Down),
+        map(literal("L"), |_| Direction::Left),
+        map(literal("R"), |_| Direction::Right),
+    ))
+}
+
+fn head_move<'a>() -> impl Parser<'a, Move> {
+    map(
+        pair(direction(), preceded(literal(" "), number())),
+        |(direction, count)| Move { direction, count },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "R 4
+U 4
+L 3
+D 1
+R 4
+D 1
+L 5
+R 2
+";
+
+    const LARGER_EXAMPLE: &str = "R 5
+U 8
+L 8
+D 3
+R 17
+D 10
+L 25
+U 20
+";
+
+    #[test]
+    fn short_rope() {
+        let moves = Day9::parse(EXAMPLE).unwrap();
+
+        assert_eq!(Day9::part1(&moves).unwrap(), "13");
+    }
+
+    #[test]
+    fn long_rope() {
+        let moves = Day9::parse(EXAMPLE).unwrap();
+        let larger = Day9::parse(LARGER_EXAMPLE).unwrap();
+
+        assert_eq!(Day9::part2(&moves).unwrap(), "1");
+        assert_eq!(Day9::part2(&larger).unwrap(), "36");
+    }
+
+    #[test]
+    fn any_length() {
+        let moves = Day9::parse(EXAMPLE).unwrap();
+
+        // A rope that is only a head visits everywhere the head goes.
+        assert_eq!(tail_positions(&moves, 1).unwrap(), 21);
+        // A rope longer than the moves are far never moves its tail.
+        assert_eq!(tail_positions(&moves, 50).unwrap(), 1);
+    }
+
+    #[test]
+    fn no_knots() {
+        let moves = Day9::parse(EXAMPLE).unwrap();
+
+        let actual = tail_positions(&moves, 0).unwrap_err().to_string();
+
+        assert_eq!(actual, "a rope needs at least one knot");
+        assert!(frames(&moves, 0).is_err());
+    }
+
+    #[test]
+    fn short_rope_frames() {
+        let moves = Day9::parse(EXAMPLE).unwrap();
+
+        let actual = frames(&moves, 2).unwrap();
+        let expected = "== R 4 ==
+
+......
+......
+......
+......
+TH....
+
+......
+......
+......
+......
+sTH...
+
+......
+......
+......
+......
+s.TH..
+
+......
+......
+......
+......
+s..TH.";
+
+        assert_eq!(actual.len(), 8);
+        assert_eq!(actual[0], expected);
+    }
+
+    #[test]
+    fn long_rope_frames() {
+        let moves = Day9::parse(LARGER_EXAMPLE).unwrap();
+
+        let actual = frames(&moves, 10).unwrap();
+        let expected = "
+
+H.........................
+1.........................
+2.........................
+3.........................
+4.........................
+5.........................
+6.........................
+7.........................
+8.........................
+9.........................
+..........................
+..........................
+..........................
+..........................
+..........................
+...........s..............
+..........................
+..........................
+..........................
+..........................
+..........................";
+
+        let last = actual.last().unwrap();
+        assert!(last.starts_with("== U 20 =="), "{last}");
+        assert!(last.ends_with(expected), "{last}");
+    }
+
+    #[test]
+    fn traces_frames() {
+        let moves = Day9::parse(EXAMPLE).unwrap();
+
+        let (_, events) = trace::collect(|| tail_positions(&moves, 2).unwrap());
+        let traced: Vec<_> = events
+            .iter()
+            .filter(|event| event.name == "frame")
+            .collect();
+
+        assert_eq!(traced.len(), 8);
+        assert_eq!(
+            traced[0].fields[2],
+            ("frame", frames(&moves, 2).unwrap()[0].as_str().into())
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_direction() {
+        let actual = Day9::parse("R 4\nX 2\n").unwrap_err().to_string();
+
+        assert!(actual.starts_with("line 2, column 1:"), "{actual}");
+    }
+}
@@ -0,0 +1,166 @@
+// `Variant` is unused when no 2022 days are enabled.
+#[allow(unused_imports)]
+use crate::{Day, Variant};
+
+#[cfg(feature = "year2022-day1")]
+pub mod day1;
+#[cfg(feature = "year2022-day10")]
+pub mod day10;
+#[cfg(feature = "year2022-day11")]
+pub mod day11;
+#[cfg(feature = "year2022-day12")]
+pub mod day12;
+#[cfg(feature = "year2022-day13")]
+pub mod day13;
+#[cfg(feature = "year2022-day14")]
+pub mod day14;
+#[cfg(feature = "year2022-day15")]
+pub mod day15;
+#[cfg(feature = "year2022-day16")]
+pub mod day16;
+#[cfg(feature = "year2022-day17")]
+pub mod day17;
+#[cfg(feature = "year2022-day18")]
+pub mod day18;
+#[cfg(feature = "year2022-day19")]
+pub mod day19;
+#[cfg(feature = "year2022-day2")]
+pub mod day2;
+#[cfg(feature = "year2022-day3")]
+pub mod day3;
+#[cfg(feature = "year2022-day4")]
+pub mod day4;
+#[cfg(feature = "year2022-day5")]
+pub mod day5;
+#[cfg(feature = "year2022-day6")]
+pub mod day6;
+#[cfg(feature = "year2022-day7")]
+pub mod day7;
+#[cfg(feature = "year2022-day8")]
+pub mod day8;
+#[cfg(feature = "year2022-day9")]
+pub mod day9;
+
+pub const DAYS: &[Day] = &[
+    #[cfg(feature = "year2022-day1")]
+    Day {
+        year: 2022,
+        number: 1,
+        variants: &[Variant::of::<day1::Day1>("default")],
+    },
+    #[cfg(feature = "year2022-day2")]
+    Day {
+        year: 2022,
+        number: 2,
+        variants: &[Variant::of::<day2::Day2>("default")],
+    },
+    #[cfg(feature = "year2022-day3")]
+    Day {
+        year: 2022,
+        number: 3,
+        variants: &[
+            Variant::of::<day3::Day3>("hashmap"),
+            Variant::of::<day3::LinearScan>("linear"),
+            Variant::of::<day3::Fast>("fast"),
+        ],
+    },
+    #[cfg(feature = "year2022-day4")]
+    Day {
+        year: 2022,
+        number: 4,
+        variants: &[Variant::of::<day4::Day4>("default")],
+    },
+    #[cfg(feature = "year2022-day5")]
+    Day {
+        year: 2022,
+        number: 5,
+        variants: &[Variant::of::<day5::Day5>("default")],
+    },
+    #[cfg(feature = "year2022-day6")]
+    Day {
+        year: 2022,
+        number: 6,
+        variants: &[
+            Variant::of::<day6::SlidingWindow>("window"),
+            Variant::of::<day6::Naive>("naive"),
+        ],
+    },
+    #[cfg(feature = "year2022-day7")]
+    Day {
+        year: 2022,
+        number: 7,
+        variants: &[Variant::of::<day7::Day7>("default")],
+    },
+    #[cfg(feature = "year2022-day8")]
+    Day {
+        year: 2022,
+        number: 8,
+        variants: &[Variant::of::<day8::Day8>("default")],
+    },
+    #[cfg(feature = "year2022-day9")]
+    Day {
+        year: 2022,
+        number: 9,
+        variants: &[Variant::of::<day9::Day9>("default")],
+    },
+    #[cfg(feature = "year2022-day10")]
+    Day {
+        year: 2022,
+        number: 10,
+        variants: &[Variant::of::<day10::Day10>("default")],
+    },
+    #[cfg(feature = "year2022-day11")]
+    Day {
+        year: 2022,
+        number: 11,
+        variants: &[Variant::of::<day11::Day11>("default")],
+    },
+    #[cfg(feature = "year2022-day12")]
+    Day {
+        year: 2022,
+        number: 12,
+        variants: &[Variant::of::<day12::Day12>("default")],
+    },
+    #[cfg(feature = "year2022-day13")]
+    Day {
+        year: 2022,
+        number: 13,
+        variants: &[Variant::of::<day13::Day13>("default")],
+    },
+    #[cfg(feature = "year2022-day14")]
+    Day {
+        year: 2022,
+        number: 14,
+        variants: &[Variant::of::<day14::Day14>("default")],
+    },
+    #[cfg(feature = "year2022-day15")]
+    Day {
+        year: 2022,
+        number: 15,
+        variants: &[Variant::of::<day15::Day15>("default")],
+    },
+    #[cfg(feature = "year2022-day16")]
+    Day {
+        year: 2022,
+        number: 16,
+        variants: &[Variant::of::<day16::Day16>("default")],
+    },
+    #[cfg(feature = "year2022-day17")]
+    Day {
+        year: 2022,
+        number: 17,
+        variants: &[Variant::of::<day17::Day17>("default")],
+    },
+    #[cfg(feature = "year2022-day18")]
+    Day {
+        year: 2022,
+        number: 18,
+        variants: &[Variant::of::<day18::Day18>("default")],
+    },
+    #[cfg(feature = "year2022-day19")]
+    Day {
+        year: 2022,
+        number: 19,
+        variants: &[Variant::of::<day19::Day19>("default")],
+    },
+];
@@ -0,0 +1,49 @@
+//! The counting allocator, installed here as it is in the binary.
+//!
+//! The counters are global, so everything is checked from a single test to
+//! keep other test threads from allocating in the middle of a measurement.
+
+use std::hint::black_box;
+
+use aoc::allocations::{measure, Counting, Stats};
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+#[test]
+fn counts_allocations() {
+    // One allocation, then a reallocation that doubles it.
+    let (bytes, grown) = measure(|| {
+        let mut bytes = black_box(Vec::<u8>::with_capacity(1024));
+        bytes.extend([0; 1024]);
+        bytes.reserve_exact(1024);
+        bytes
+    });
+    assert_eq!(bytes.capacity(), 2048);
+    assert_eq!(
+        grown,
+        Stats {
+            allocations: 2,
+            bytes: 1024 + 2048,
+            peak: 2048,
+        }
+    );
+
+    // The peak is taken from what is live when measuring starts, so the
+    // bytes still held above do not count again.
+    let ((), freed) = measure(|| {
+        drop(black_box(Vec::<u8>::with_capacity(512)));
+        drop(bytes);
+    });
+    assert_eq!(
+        freed,
+        Stats {
+            allocations: 1,
+            bytes: 512,
+            peak: 512,
+        }
+    );
+
+    let ((), nothing) = measure(|| ());
+    assert_eq!(nothing, Stats::default());
+}